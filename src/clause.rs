@@ -0,0 +1,270 @@
+use crate::cte::parse_with;
+use crate::tokens::{first_leaf, is_comment_or_hint, last_leaf, next_significant};
+use crate::{Position, Statement, Token};
+use std::ops::Range;
+
+/// The kind of a clause.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClauseKind {
+    /// `WITH ...` (including all the common table expressions).
+    With,
+    /// `SELECT ...`
+    Select,
+    /// `FROM ...`
+    From,
+    /// `WHERE ...`
+    Where,
+    /// `GROUP BY ...`
+    GroupBy,
+    /// `HAVING ...`
+    Having,
+    /// `WINDOW ...`
+    Window,
+    /// `ORDER BY ...`
+    OrderBy,
+    /// `LIMIT ...`
+    Limit,
+    /// `OFFSET ...`
+    Offset,
+    /// `FETCH { FIRST | NEXT } ...`
+    Fetch,
+    /// `RETURNING ...`
+    Returning,
+    /// `ON CONFLICT ...` (PostgreSQL, SQLite) or `ON DUPLICATE KEY UPDATE ...` (MySQL).
+    OnConflict,
+    /// `SET ...`
+    Set,
+    /// `VALUES ...`
+    Values,
+    /// A set operator combining two queries: `UNION`, `INTERSECT`, `EXCEPT` or `MINUS` (with `ALL` or `DISTINCT`).
+    SetOperator,
+    /// Any tokens not belonging to one of the other clauses (ex: `INSERT INTO t (a, b)`, `DELETE`, `UPDATE t`).
+    Other,
+}
+
+/// A clause of a statement.
+#[derive(Debug)]
+pub struct Clause<'a, 's> {
+    /// The kind of the clause.
+    pub kind: ClauseKind,
+
+    /// The tokens of the clause, starting with the keyword(s) of the clause.
+    pub tokens: &'a [Token<'s>],
+
+    /// The position of the first character of the clause.
+    pub start: Position,
+
+    /// The position of the last character of the clause.
+    pub end: Position,
+}
+
+impl<'s> Statement<'s> {
+    /// Splits the statement into clauses.
+    ///
+    /// Only the top-level tokens of the statement are considered, clauses of sub-queries are part of the clause of the
+    /// parent query where they appear. Leading comments and the statement delimiter are not part of any clause.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use loose_sqlparser::{loose_sqlparse, ClauseKind};
+    /// let stmt = loose_sqlparse("SELECT a FROM t WHERE b IN (SELECT 1) ORDER BY a LIMIT 10;").next().unwrap();
+    /// let clauses = stmt.clauses();
+    /// assert_eq!(
+    ///     clauses.iter().map(|c| c.kind).collect::<Vec<_>>(),
+    ///     [ClauseKind::Select, ClauseKind::From, ClauseKind::Where, ClauseKind::OrderBy, ClauseKind::Limit]
+    /// );
+    /// assert_eq!(clauses[2].tokens.len(), 6);
+    /// assert_eq!(clauses[4].start.column, 50);
+    /// assert_eq!(clauses[4].end.column, 57);
+    /// ```
+    pub fn clauses(&self) -> Vec<Clause<'_, 's>> {
+        let tokens: &[Token<'s>] = &self.tokens;
+        split_clauses(tokens)
+            .into_iter()
+            .filter_map(|(kind, range)| {
+                let clause_tokens = &tokens[range];
+                let start = first_leaf(clause_tokens)?.start.clone();
+                let end = last_leaf(clause_tokens)?.end.clone();
+                Some(Clause { kind, tokens: clause_tokens, start, end })
+            })
+            .collect()
+    }
+}
+
+// Splits the given tokens into clauses, see [`Statement::clauses`]. Returns the kind and the range of tokens of each
+// clause.
+pub(crate) fn split_clauses(tokens: &[Token]) -> Vec<(ClauseKind, Range<usize>)> {
+    let Some(first) = next_significant(tokens, 0) else {
+        return Vec::new();
+    };
+
+    // Collect the index of the first token of each clause.
+    let mut boundaries: Vec<(usize, ClauseKind)> = Vec::new();
+    let mut index = first;
+    if tokens[first].is_keyword("WITH") {
+        boundaries.push((first, ClauseKind::With));
+        index = parse_with(tokens).map_or(tokens.len(), |with| tokens.len() - with.main.len());
+    }
+    let mut previous: Option<&Token> = None;
+    while index < tokens.len() {
+        let token = &tokens[index];
+        let next = next_significant(tokens, index + 1).map(|i| &tokens[i]);
+        let is_followed_by = |keyword: &str| next.is_some_and(|t| t.is_keyword(keyword));
+        let in_conflict_clause = boundaries.last().is_some_and(|&(_, kind)| kind == ClauseKind::OnConflict);
+        let kind = if is_comment_or_hint(token) {
+            None
+        } else if token.is_keyword("SELECT") {
+            Some(ClauseKind::Select)
+        } else if token.is_keyword("FROM") && !previous.is_some_and(|t| t.is_keyword("DISTINCT")) {
+            Some(ClauseKind::From)
+        } else if token.is_keyword("WHERE") && !in_conflict_clause {
+            Some(ClauseKind::Where)
+        } else if token.is_keyword("GROUP") && is_followed_by("BY") {
+            Some(ClauseKind::GroupBy)
+        } else if token.is_keyword("HAVING") {
+            Some(ClauseKind::Having)
+        } else if token.is_keyword("WINDOW") {
+            Some(ClauseKind::Window)
+        } else if token.is_keyword("ORDER") && is_followed_by("BY") {
+            Some(ClauseKind::OrderBy)
+        } else if token.is_keyword("LIMIT") {
+            Some(ClauseKind::Limit)
+        } else if token.is_keyword("OFFSET") {
+            Some(ClauseKind::Offset)
+        } else if token.is_keyword("FETCH") && index != first && (is_followed_by("FIRST") || is_followed_by("NEXT")) {
+            Some(ClauseKind::Fetch)
+        } else if token.is_keyword("RETURNING") {
+            Some(ClauseKind::Returning)
+        } else if token.is_keyword("ON") && (is_followed_by("CONFLICT") || is_followed_by("DUPLICATE")) {
+            Some(ClauseKind::OnConflict)
+        } else if token.is_keyword("SET") && index != first && !in_conflict_clause {
+            Some(ClauseKind::Set)
+        } else if token.is_keyword("VALUES") {
+            Some(ClauseKind::Values)
+        } else if ["UNION", "INTERSECT", "EXCEPT", "MINUS"].iter().any(|keyword| token.is_keyword(keyword)) {
+            Some(ClauseKind::SetOperator)
+        } else if index == first {
+            Some(ClauseKind::Other)
+        } else {
+            None
+        };
+        if let Some(kind) = kind {
+            boundaries.push((index, kind));
+        }
+        if !is_comment_or_hint(token) {
+            previous = Some(token);
+        }
+        index += 1;
+    }
+
+    // Build the clauses from the boundaries.
+    let end = tokens.iter().rposition(|t| !t.is_statement_delimiter()).map_or(0, |i| i + 1);
+    boundaries
+        .iter()
+        .enumerate()
+        .map(|(i, &(start, kind))| {
+            let clause_end = boundaries.get(i + 1).map_or(end, |&(next_start, _)| next_start.min(end));
+            (kind, start..clause_end.max(start))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{loose_sqlparse, ClauseKind};
+
+    // Returns the clauses of the first statement of the given SQL as a list of (kind, tokens).
+    fn clauses(sql: &str) -> Vec<(ClauseKind, String)> {
+        let stmt = loose_sqlparse(sql).next().unwrap();
+        stmt.clauses().iter().map(|c| (c.kind, c.tokens.iter().flat_map(|t| t.as_str_array()).collect())).collect()
+    }
+
+    #[test]
+    fn test_clauses_select() {
+        assert_eq!(
+            clauses(
+                "WITH x AS (SELECT 1) SELECT a, b FROM t JOIN x ON x.a = t.a WHERE a IS DISTINCT FROM b GROUP BY a \
+                 HAVING count(*) > 1 WINDOW w AS (ORDER BY a) ORDER BY a OFFSET 1 ROWS FETCH FIRST 1 ROWS ONLY;"
+            ),
+            [
+                (ClauseKind::With, "WITHxAS(SELECT1)".to_string()),
+                (ClauseKind::Select, "SELECTa,b".to_string()),
+                (ClauseKind::From, "FROMtJOINxONx.a=t.a".to_string()),
+                (ClauseKind::Where, "WHEREaISDISTINCTFROMb".to_string()),
+                (ClauseKind::GroupBy, "GROUPBYa".to_string()),
+                (ClauseKind::Having, "HAVINGcount(*)>1".to_string()),
+                (ClauseKind::Window, "WINDOWwAS(ORDERBYa)".to_string()),
+                (ClauseKind::OrderBy, "ORDERBYa".to_string()),
+                (ClauseKind::Offset, "OFFSET1ROWS".to_string()),
+                (ClauseKind::Fetch, "FETCHFIRST1ROWSONLY".to_string()),
+            ]
+        );
+        assert_eq!(
+            clauses("SELECT 1 UNION ALL SELECT 2 LIMIT 1"),
+            [
+                (ClauseKind::Select, "SELECT1".to_string()),
+                (ClauseKind::SetOperator, "UNIONALL".to_string()),
+                (ClauseKind::Select, "SELECT2".to_string()),
+                (ClauseKind::Limit, "LIMIT1".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_clauses_dml() {
+        assert_eq!(
+            clauses(
+                "INSERT INTO t (a) VALUES (1) ON CONFLICT (a) WHERE a > 0 DO UPDATE SET a = 2 WHERE t.a = 1 \
+                 RETURNING a"
+            ),
+            [
+                (ClauseKind::Other, "INSERTINTOt(a)".to_string()),
+                (ClauseKind::Values, "VALUES(1)".to_string()),
+                (ClauseKind::OnConflict, "ONCONFLICT(a)WHEREa>0DOUPDATESETa=2WHEREt.a=1".to_string()),
+                (ClauseKind::Returning, "RETURNINGa".to_string()),
+            ]
+        );
+        assert_eq!(
+            clauses("UPDATE t SET a = 1 FROM u WHERE t.id = u.id"),
+            [
+                (ClauseKind::Other, "UPDATEt".to_string()),
+                (ClauseKind::Set, "SETa=1".to_string()),
+                (ClauseKind::From, "FROMu".to_string()),
+                (ClauseKind::Where, "WHEREt.id=u.id".to_string()),
+            ]
+        );
+        assert_eq!(
+            clauses("INSERT INTO t SELECT 1 ON DUPLICATE KEY UPDATE a = 1"),
+            [
+                (ClauseKind::Other, "INSERTINTOt".to_string()),
+                (ClauseKind::Select, "SELECT1".to_string()),
+                (ClauseKind::OnConflict, "ONDUPLICATEKEYUPDATEa=1".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_clauses_others() {
+        assert_eq!(clauses("SET search_path = x"), [(ClauseKind::Other, "SETsearch_path=x".to_string())]);
+        assert_eq!(
+            clauses("FETCH NEXT FROM c"),
+            [(ClauseKind::Other, "FETCHNEXT".to_string()), (ClauseKind::From, "FROMc".to_string())]
+        );
+        assert_eq!(
+            clauses("/* leading */ SELECT 1 -- trailing\n;"),
+            [(ClauseKind::Select, "SELECT1-- trailing".to_string())]
+        );
+        assert!(clauses("/* empty */;").is_empty());
+    }
+
+    #[test]
+    fn test_clauses_positions() {
+        let stmt = loose_sqlparse("SELECT (1\n  + 2)\nFROM t").next().unwrap();
+        let clauses = stmt.clauses();
+        assert_eq!((clauses[0].start.line, clauses[0].start.column), (1, 1));
+        assert_eq!((clauses[0].end.line, clauses[0].end.column), (2, 6));
+        assert_eq!((clauses[1].start.line, clauses[1].start.column), (3, 1));
+        assert_eq!((clauses[1].end.line, clauses[1].end.column), (3, 6));
+    }
+}
@@ -0,0 +1,156 @@
+use crate::tokens::leaves;
+use crate::{Statement, Token, TokenValue};
+
+/// The kind of a comment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommentKind {
+    /// A comment running until the end of the line (`-- ...` or `# ...`).
+    Line,
+    /// A comment enclosed in `/*` and `*/`.
+    Block,
+}
+
+/// The placement of a comment in a statement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommentPlacement {
+    /// The comment precedes the statement.
+    Leading,
+    /// The comment follows the statement (before or after the statement delimiter).
+    Trailing,
+    /// The comment is inside the statement.
+    Inner,
+}
+
+/// A comment of a statement, see [`Statement::comments`].
+#[derive(Debug, Clone)]
+pub struct Comment<'a, 's> {
+    /// The token of the comment.
+    pub token: &'a Token<'s>,
+
+    /// The kind of the comment.
+    pub kind: CommentKind,
+
+    /// The placement of the comment in the statement.
+    pub placement: CommentPlacement,
+
+    /// The text of the comment, without the comment delimiters and the surrounding whitespace.
+    pub text: &'s str,
+}
+
+impl<'s> Statement<'s> {
+    /// Returns the comments of the statement in the order they appear (including those nested in parentheses).
+    ///
+    /// The comments preceding the statement are not part of its tokens, they are returned with the
+    /// [`CommentPlacement::Leading`] placement. The comments following the statement delimiter on the same line are
+    /// trailing comments of the statement, the comments on the next lines are leading comments of the next statement.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use loose_sqlparser::{loose_sqlparse, CommentKind, CommentPlacement};
+    /// let stmt = loose_sqlparse("-- name: GetUser :one\nSELECT * FROM users /* owner: team-x */ WHERE id = ?; -- end")
+    ///     .next()
+    ///     .unwrap();
+    /// let comments = stmt.comments();
+    /// assert_eq!(comments.len(), 3);
+    /// assert_eq!(comments[0].text, "name: GetUser :one");
+    /// assert_eq!((comments[0].kind, comments[0].placement), (CommentKind::Line, CommentPlacement::Leading));
+    /// assert_eq!(comments[1].text, "owner: team-x");
+    /// assert_eq!((comments[1].kind, comments[1].placement), (CommentKind::Block, CommentPlacement::Inner));
+    /// assert_eq!(comments[2].placement, CommentPlacement::Trailing);
+    /// ```
+    pub fn comments(&self) -> Vec<Comment<'_, 's>> {
+        let mut comments = self.leading_comments();
+        let tokens = leaves(&self.tokens);
+        let is_code = |t: &&Token| !t.is_comment() && !t.is_statement_delimiter();
+        let first = tokens.iter().position(is_code).unwrap_or(tokens.len());
+        let last = tokens.iter().rposition(is_code);
+        comments.extend(tokens.iter().enumerate().filter_map(|(index, token)| {
+            let placement = if index < first {
+                CommentPlacement::Leading
+            } else if last.is_some_and(|last| index > last) {
+                CommentPlacement::Trailing
+            } else {
+                CommentPlacement::Inner
+            };
+            comment(token, placement)
+        }));
+        comments
+    }
+
+    /// Returns the comments preceding the statement, see [`Statement::comments`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use loose_sqlparser::loose_sqlparse;
+    /// let stmt = loose_sqlparse("/* owner: team-x */\n-- Returns a user\nSELECT * FROM users -- all").next().unwrap();
+    /// let comments = stmt.leading_comments();
+    /// assert_eq!(comments.iter().map(|c| c.text).collect::<Vec<_>>(), ["owner: team-x", "Returns a user"]);
+    /// assert_eq!(stmt.sql(), "SELECT * FROM users -- all");
+    /// ```
+    pub fn leading_comments(&self) -> Vec<Comment<'_, 's>> {
+        self.leading.iter().filter_map(|token| comment(token, CommentPlacement::Leading)).collect()
+    }
+}
+
+// Returns the comment of a token, `None` if the token is not a comment.
+fn comment<'a, 's>(token: &'a Token<'s>, placement: CommentPlacement) -> Option<Comment<'a, 's>> {
+    let TokenValue::Comment(comment) = token.value else {
+        return None;
+    };
+    let (kind, text) = match comment.strip_prefix("/*") {
+        Some(text) => (CommentKind::Block, text.strip_suffix("*/").unwrap_or(text)),
+        None => (CommentKind::Line, comment.strip_prefix("--").or(comment.strip_prefix('#')).unwrap_or(comment)),
+    };
+    Some(Comment { token, kind, placement, text: text.trim() })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{loose_sqlparse, CommentKind, CommentPlacement};
+
+    // Returns the (kind, placement, text) of the comments of each statement of the given SQL.
+    fn comments(sql: &str) -> Vec<Vec<(CommentKind, CommentPlacement, &str)>> {
+        loose_sqlparse(sql)
+            .map(|stmt| stmt.comments().iter().map(|c| (c.kind, c.placement, c.text)).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_comments() {
+        use CommentKind::*;
+        use CommentPlacement::*;
+        assert_eq!(
+            comments("# one\nSELECT (1 /* two */) -- three\n;/* four\n */SELECT 2 /* unterminated"),
+            [
+                vec![(Line, Leading, "one"), (Block, Inner, "two"), (Line, Trailing, "three")],
+                vec![(Block, Leading, "four"), (Block, Trailing, "unterminated")],
+            ]
+        );
+        assert_eq!(comments("/* only */ -- comments"), [vec![(Block, Leading, "only"), (Line, Leading, "comments")]]);
+        assert_eq!(comments("SELECT 1; --\n"), [vec![(Line, Trailing, "")]]);
+        assert_eq!(
+            comments("SELECT 1; /* one */ # two\n-- three\nSELECT 2; SELECT 3; -- four"),
+            [
+                vec![(Block, Trailing, "one"), (Line, Trailing, "two")],
+                vec![(Line, Leading, "three")],
+                vec![(Line, Trailing, "four")],
+            ]
+        );
+        // The rest of the line is not only made of comments.
+        assert_eq!(comments("SELECT 1; /* one */ SELECT 2"), [vec![], vec![(Block, Leading, "one")]]);
+    }
+
+    #[test]
+    fn test_leading_comments() {
+        let stmt = loose_sqlparse("-- name: GetUser :one\n-- Returns a user.\nSELECT /* inner */ 1").next().unwrap();
+        let leading = stmt.leading_comments();
+        assert_eq!(leading.len(), 2);
+        assert_eq!(leading[0].text, "name: GetUser :one");
+        assert_eq!(leading[1].token.start.line, 2);
+        // The leading comments are not part of the tokens of the statement.
+        assert_eq!(stmt.tokens()[0].to_string(), "SELECT");
+        assert_eq!(stmt.sql(), "SELECT /* inner */ 1");
+    }
+}
@@ -0,0 +1,220 @@
+use crate::tokens::next_significant;
+use crate::{Statement, Token, TokenValue};
+
+// Keywords that can start the main statement following the common table expressions.
+const MAIN_STATEMENT_KEYWORDS: [&str; 7] = ["SELECT", "INSERT", "UPDATE", "DELETE", "MERGE", "VALUES", "TABLE"];
+
+/// A common table expression (CTE) declared in a `WITH` clause.
+///
+/// ```sql
+/// WITH RECURSIVE tree (id, parent_id) AS MATERIALIZED (SELECT ...) SELECT ...
+///                ^^^^ ^^^^^^^^^^^^^^^    ^^^^^^^^^^^^  ^^^^^^^^^^
+///                name columns            materialized  body
+/// ```
+#[derive(Debug)]
+pub struct Cte<'a, 's> {
+    /// The name of the CTE (an identifier or a quoted identifier).
+    pub name: &'a Token<'s>,
+
+    /// The optional list of column names declared after the name of the CTE.
+    pub columns: Vec<&'a Token<'s>>,
+
+    /// Whether the `WITH` clause declaring the CTE is `RECURSIVE`.
+    pub recursive: bool,
+
+    /// `Some(true)` for `MATERIALIZED`, `Some(false)` for `NOT MATERIALIZED` and `None` if not specified.
+    pub materialized: Option<bool>,
+
+    /// The [`crate::TokenValue::Fragment`] holding the body of the CTE (the tokens between the parentheses).
+    pub body: &'a Token<'s>,
+}
+
+/// The `WITH` clause of a statement and the main statement that follows.
+#[derive(Debug)]
+pub struct WithClause<'a, 's> {
+    /// The common table expressions, in the order they are declared.
+    pub ctes: Vec<Cte<'a, 's>>,
+
+    /// The tokens of the main statement following the last CTE (including the statement delimiter if any).
+    pub main: &'a [Token<'s>],
+}
+
+impl<'s> Statement<'s> {
+    /// Returns the common table expressions declared by the `WITH` clause of the statement.
+    ///
+    /// Returns `None` if the statement does not start with a `WITH` clause.
+    /// Because the parser is not validating, a malformed `WITH` clause will stop the collection of the CTEs and all
+    /// remaining tokens are considered as the main statement.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use loose_sqlparser::loose_sqlparse;
+    /// let stmt = loose_sqlparse("WITH a (x) AS (SELECT 1), b AS (SELECT 2) SELECT * FROM a, b").next().unwrap();
+    /// let with = stmt.ctes().unwrap();
+    /// assert_eq!(with.ctes.len(), 2);
+    /// assert_eq!(with.ctes[0].name.to_string(), "a");
+    /// assert_eq!(with.ctes[0].body.children().unwrap().as_str_array(), ["SELECT", "1"]);
+    /// assert_eq!(with.main[0].to_string(), "SELECT");
+    /// ```
+    pub fn ctes(&self) -> Option<WithClause<'_, 's>> {
+        parse_with(&self.tokens)
+    }
+}
+
+// Parses the `WITH` clause starting the given tokens, see [`Statement::ctes`].
+pub(crate) fn parse_with<'a, 's>(tokens: &'a [Token<'s>]) -> Option<WithClause<'a, 's>> {
+    let mut index = next_significant(tokens, 0).filter(|&i| tokens[i].is_keyword("WITH"))?;
+    let mut ctes = Vec::new();
+
+    index = next_significant(tokens, index + 1).unwrap_or(tokens.len());
+    let recursive = index < tokens.len() && tokens[index].is_keyword("RECURSIVE");
+    if recursive {
+        index = next_significant(tokens, index + 1).unwrap_or(tokens.len());
+    }
+
+    while index < tokens.len() {
+        let name = &tokens[index];
+        if !(name.is_identifier_or_keyword() || name.is_quoted_identifier_or_constant())
+            || MAIN_STATEMENT_KEYWORDS.iter().any(|keyword| name.is_keyword(keyword))
+        {
+            break;
+        }
+        let mut next = next_significant(tokens, index + 1);
+
+        // The optional list of columns.
+        let mut columns = Vec::new();
+        if let Some((fragment, after)) = next.and_then(|i| parenthesized(tokens, i)) {
+            columns = fragment
+                .children()
+                .map(|children| {
+                    children
+                        .iter()
+                        .filter(|t| t.is_identifier_or_keyword() || t.is_quoted_identifier_or_constant())
+                        .collect()
+                })
+                .unwrap_or_default();
+            next = next_significant(tokens, after);
+        }
+
+        // AS [[NOT] MATERIALIZED]
+        match next {
+            Some(i) if tokens[i].is_keyword("AS") => next = next_significant(tokens, i + 1),
+            _ => break,
+        }
+        let mut materialized = None;
+        if let Some(i) = next.filter(|&i| tokens[i].is_keyword("NOT")) {
+            if let Some(j) = next_significant(tokens, i + 1).filter(|&j| tokens[j].is_keyword("MATERIALIZED")) {
+                materialized = Some(false);
+                next = next_significant(tokens, j + 1);
+            }
+        } else if let Some(i) = next.filter(|&i| tokens[i].is_keyword("MATERIALIZED")) {
+            materialized = Some(true);
+            next = next_significant(tokens, i + 1);
+        }
+
+        // The body of the CTE.
+        let Some((body, after)) = next.and_then(|i| parenthesized(tokens, i)) else {
+            break;
+        };
+        ctes.push(Cte { name, columns, recursive, materialized, body });
+
+        // Skip the optional `SEARCH` and `CYCLE` clauses (PostgreSQL) until the next CTE or the main statement.
+        index = next_significant(tokens, after).unwrap_or(tokens.len());
+        while index < tokens.len()
+            && !tokens[index].is_comma()
+            && !tokens[index].is_parenthesis()
+            && !MAIN_STATEMENT_KEYWORDS.iter().any(|keyword| tokens[index].is_keyword(keyword))
+        {
+            index = next_significant(tokens, index + 1).unwrap_or(tokens.len());
+        }
+        if index < tokens.len() && tokens[index].is_comma() {
+            index = next_significant(tokens, index + 1).unwrap_or(tokens.len());
+        } else {
+            break;
+        }
+    }
+
+    Some(WithClause { ctes, main: &tokens[index.min(tokens.len())..] })
+}
+
+// If the token at `index` is an opening parenthesis, returns the fragment that follows it and the index of the
+// token following the closing parenthesis.
+fn parenthesized<'a, 's>(tokens: &'a [Token<'s>], index: usize) -> Option<(&'a Token<'s>, usize)> {
+    if !matches!(tokens[index].value, TokenValue::Any("(")) || !tokens.get(index + 1).is_some_and(|t| t.is_fragment()) {
+        return None;
+    }
+    let after = match tokens.get(index + 2) {
+        Some(t) if t.is_parenthesis() => index + 3,
+        _ => index + 2,
+    };
+    Some((&tokens[index + 1], after))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::loose_sqlparse;
+
+    #[test]
+    fn test_ctes() {
+        let stmt = loose_sqlparse(
+            "WITH RECURSIVE t1 (a, b) AS (SELECT 1, 2), \"T2\" AS NOT MATERIALIZED (SELECT 3), t3 AS MATERIALIZED (\
+             SELECT (4)) SELECT * FROM t1;",
+        )
+        .next()
+        .unwrap();
+        let with = stmt.ctes().unwrap();
+        assert_eq!(with.ctes.len(), 3);
+
+        assert_eq!(with.ctes[0].name.to_string(), "t1");
+        assert_eq!(with.ctes[0].columns.iter().map(|t| t.to_string()).collect::<Vec<_>>(), ["a", "b"]);
+        assert!(with.ctes[0].recursive);
+        assert_eq!(with.ctes[0].materialized, None);
+        assert_eq!(with.ctes[0].body.children().unwrap().as_str_array(), ["SELECT", "1", ",", "2"]);
+
+        assert_eq!(with.ctes[1].name.to_string(), "\"T2\"");
+        assert!(with.ctes[1].columns.is_empty());
+        assert_eq!(with.ctes[1].materialized, Some(false));
+        assert_eq!(with.ctes[1].body.children().unwrap().as_str_array(), ["SELECT", "3"]);
+
+        assert_eq!(with.ctes[2].materialized, Some(true));
+        assert_eq!(with.ctes[2].body.children().unwrap().as_str_array(), ["SELECT", "(", "4", ")"]);
+
+        let main: Vec<_> = with.main.iter().flat_map(|t| t.as_str_array()).collect();
+        assert_eq!(main, ["SELECT", "*", "FROM", "t1", ";"]);
+    }
+
+    #[test]
+    fn test_ctes_with_comments_and_search_clause() {
+        let stmt = loose_sqlparse(
+            "/* c */ WITH -- c\n t AS (SELECT 1) SEARCH DEPTH FIRST BY id SET ord, u AS (SELECT 2) DELETE FROM x",
+        )
+        .next()
+        .unwrap();
+        let with = stmt.ctes().unwrap();
+        assert_eq!(with.ctes.iter().map(|c| c.name.to_string()).collect::<Vec<_>>(), ["t", "u"]);
+        assert!(!with.ctes[0].recursive);
+        assert_eq!(with.main.iter().map(|t| t.to_string()).collect::<Vec<_>>(), ["DELETE", "FROM", "x"]);
+    }
+
+    #[test]
+    fn test_ctes_not_a_with_statement() {
+        assert!(loose_sqlparse("SELECT 1").next().unwrap().ctes().is_none());
+        assert!(loose_sqlparse("-- WITH\n").next().unwrap().ctes().is_none());
+    }
+
+    #[test]
+    fn test_ctes_malformed() {
+        // Missing body: the CTE is ignored and the remaining tokens are considered as the main statement.
+        let stmt = loose_sqlparse("WITH t AS SELECT 1").next().unwrap();
+        let with = stmt.ctes().unwrap();
+        assert!(with.ctes.is_empty());
+        assert_eq!(with.main.iter().map(|t| t.to_string()).collect::<Vec<_>>(), ["t", "AS", "SELECT", "1"]);
+
+        // Missing closing parenthesis.
+        let stmt = loose_sqlparse("WITH t AS (SELECT 1").next().unwrap();
+        let with = stmt.ctes().unwrap();
+        assert_eq!(with.ctes.len(), 1);
+        assert!(with.main.is_empty());
+    }
+}
@@ -0,0 +1,115 @@
+use crate::{ParameterStyle, Position};
+use std::ops::Range;
+
+/// Errors reported by the helper functions interpreting the tokens of a statement.
+///
+/// The tokenizer itself never fails, errors are only reported by functions that need the statement to follow some
+/// rules to give a meaningful result, or as diagnostics of a statement (see [`crate::Statement::diagnostics`]).
+#[derive(Debug, Clone)]
+pub enum Error {
+    /// A statement is using parameter markers of different styles (ex: `?` and `:name`).
+    MixedParameterStyles {
+        /// The style of the first parameter marker found in the statement.
+        expected: ParameterStyle,
+        /// The style of the first parameter marker not matching the expected style.
+        found: ParameterStyle,
+        /// The position of the parameter marker not matching the expected style.
+        position: Position,
+    },
+
+    /// Parameter markers cannot be rewritten from a style to another (ex: from `?` to `:name`).
+    UnsupportedParameterConversion {
+        /// The style of the parameter markers of the statement.
+        from: ParameterStyle,
+        /// The requested style.
+        to: ParameterStyle,
+    },
+
+    /// An edit is not a valid range of the input (out of bounds or not on a character boundary).
+    InvalidEditRange {
+        /// The range of the edit.
+        range: Range<usize>,
+    },
+
+    /// Two edits of an [`crate::EditSet`] overlap.
+    OverlappingEdits {
+        /// The range of the edit already in the set.
+        first: Range<usize>,
+        /// The range of the edit overlapping the first one.
+        second: Range<usize>,
+    },
+
+    /// A statement of a named query file has no `-- name:` annotation.
+    MissingQueryName {
+        /// The start position of the statement.
+        position: Position,
+    },
+
+    /// Several queries of a named query file have the same name.
+    DuplicateQueryName {
+        /// The name of the queries.
+        name: String,
+        /// The start position of the first query with this name.
+        first: Position,
+        /// The start position of the query reusing the name.
+        second: Position,
+    },
+
+    /// A named query of a named query file has no statement (ex: `-- name: FindUser` followed by `;`).
+    EmptyQuery {
+        /// The name of the query.
+        name: String,
+        /// The start position of the query.
+        position: Position,
+    },
+
+    /// A block of a statement is nested deeper than [`crate::Options::max_depth`], its content is tokenized flat.
+    MaxDepthExceeded {
+        /// The maximum nesting depth.
+        max_depth: usize,
+        /// The position of the first opening character exceeding the maximum depth.
+        position: Position,
+    },
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::MixedParameterStyles { expected, found, position } if expected == found => write!(
+                f,
+                "{:?} parameter marker with a different prefix found at {}:{}",
+                found, position.line, position.column
+            ),
+            Error::MixedParameterStyles { expected, found, position } => write!(
+                f,
+                "{:?} parameter marker found at {}:{} in a statement using {:?} parameter markers",
+                found, position.line, position.column, expected
+            ),
+            Error::UnsupportedParameterConversion { from, to } => {
+                write!(f, "{:?} parameter markers cannot be rewritten as {:?} parameter markers", from, to)
+            }
+            Error::InvalidEditRange { range } => write!(f, "invalid edit range {:?}", range),
+            Error::OverlappingEdits { first, second } => {
+                write!(f, "edit of range {:?} overlaps the edit of range {:?}", second, first)
+            }
+            Error::MissingQueryName { position } => {
+                write!(f, "query at {}:{} has no name", position.line, position.column)
+            }
+            Error::DuplicateQueryName { name, first, second } => write!(
+                f,
+                "query '{}' at {}:{} is already defined at {}:{}",
+                name, second.line, second.column, first.line, first.column
+            ),
+            Error::EmptyQuery { name, position } => {
+                write!(f, "query '{}' at {}:{} has no statement", name, position.line, position.column)
+            }
+            Error::MaxDepthExceeded { max_depth, position } => write!(
+                f,
+                "nesting deeper than {} levels at {}:{} is tokenized flat",
+                max_depth, position.line, position.column
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
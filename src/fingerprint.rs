@@ -0,0 +1,232 @@
+use crate::keywords::is_keyword;
+use crate::tokens::{is_string_constant, next_significant};
+use crate::{Statement, Token, TokenValue};
+use std::borrow::Cow;
+
+// The placeholder replacing constants and parameter markers in a normalized statement.
+const PLACEHOLDER: &str = "?";
+
+// The keywords followed by an operand, a `-` or `+` following them is the sign of a numeric constant.
+const OPERAND_KEYWORDS: [&str; 29] = [
+    "select",
+    "where",
+    "and",
+    "or",
+    "not",
+    "xor",
+    "on",
+    "case",
+    "when",
+    "then",
+    "else",
+    "between",
+    "like",
+    "ilike",
+    "is",
+    "in",
+    "values",
+    "set",
+    "having",
+    "limit",
+    "offset",
+    "return",
+    "returning",
+    "distinct",
+    "all",
+    "any",
+    "some",
+    "interval",
+    "escape",
+];
+
+// The placeholder replacing a list of constants (ex: `IN (1, 2, 3)`) in a normalized statement.
+const LIST_PLACEHOLDER: &str = "...";
+
+/// The fingerprint of a statement, see [`Statement::fingerprint`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fingerprint {
+    /// The normalized text of the statement.
+    pub normalized: String,
+
+    /// A 64-bit hash of the normalized text.
+    ///
+    /// The hash is stable: the same normalized text always gives the same hash, regardless of the platform or the
+    /// version of the library.
+    pub hash: u64,
+}
+
+impl Statement<'_> {
+    /// Returns the fingerprint of the statement.
+    ///
+    /// Statements that only differ by the value of their constants, their comments, their whitespace or the case of
+    /// their keywords share the same fingerprint:
+    /// - numeric and string constants as well as parameter markers are replaced by `?`,
+    /// - lists of constants (ex: `IN (1, 2, 3)`) are replaced by `(...)`,
+    /// - keywords are lowercased (identifiers are left untouched),
+    /// - comments, optimizer hints, the statement delimiter and the inline data of `COPY ... FROM STDIN` are removed,
+    /// - tokens are separated by a single space (except around parentheses, commas, dots and `::`).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use loose_sqlparser::loose_sqlparse;
+    /// let stmts: Vec<_> = loose_sqlparse(
+    ///     "SELECT * FROM users WHERE id IN (1, 2, 3) AND name = 'x';
+    ///      select *
+    ///        from users where id in (4) and name = 'y' -- comment",
+    /// )
+    /// .collect();
+    /// assert_eq!(stmts[0].fingerprint().normalized, "select * from users where id in (...) and name = ?");
+    /// assert_eq!(stmts[0].fingerprint(), stmts[1].fingerprint());
+    /// ```
+    pub fn fingerprint(&self) -> Fingerprint {
+        let mut pieces = Vec::new();
+        normalize(&self.tokens, &mut pieces);
+        let mut normalized = String::new();
+        for (i, piece) in pieces.iter().enumerate() {
+            if i > 0 && needs_space(&pieces[i - 1], piece) {
+                normalized.push(' ');
+            }
+            normalized.push_str(piece);
+        }
+        let hash = fnv1a(normalized.as_bytes());
+        Fingerprint { normalized, hash }
+    }
+}
+
+// Collects the normalized pieces of text of the given tokens.
+fn normalize<'a>(tokens: &'a [Token], pieces: &mut Vec<Cow<'a, str>>) {
+    for (index, token) in tokens.iter().enumerate() {
+        match &token.value {
+            TokenValue::Comment(_)
+            | TokenValue::OptimizerHint(_)
+            | TokenValue::StatementDelimiter(_)
+            | TokenValue::CopyData(_) => {}
+            TokenValue::NumericConstant(_) => pieces.push(PLACEHOLDER.into()),
+            TokenValue::QuotedIdentifierOrConstant(value) => {
+                pieces.push(if is_string_constant(token) { PLACEHOLDER } else { value }.into())
+            }
+            TokenValue::ParameterMarker(_) => pieces.push(PLACEHOLDER.into()),
+            TokenValue::IdentifierOrKeyword(value) if is_keyword(value) => {
+                pieces.push(value.to_ascii_lowercase().into())
+            }
+            TokenValue::Operator(value) if *value == "-" || *value == "+" => {
+                // The sign of a numeric constant is part of the constant (ex: `-1`), but a binary operator is not (ex:
+                // `a - 1`).
+                let is_unary = pieces.last().is_none_or(|previous| {
+                    previous == "(" || previous == "," || is_operand_keyword(previous) || is_operator(previous)
+                });
+                let is_sign = is_unary
+                    && next_significant(tokens, index + 1).is_some_and(|next| tokens[next].is_numeric_constant());
+                if !is_sign {
+                    pieces.push((*value).into());
+                }
+            }
+            TokenValue::Fragment(children, _) => {
+                let is_in_list = pieces.len() >= 2
+                    && pieces[pieces.len() - 1] == "("
+                    && pieces[pieces.len() - 2] == "in"
+                    && is_constant_list(children);
+                if is_in_list {
+                    pieces.push(LIST_PLACEHOLDER.into());
+                } else {
+                    normalize(children, pieces);
+                }
+            }
+            _ => pieces.push(token.value.as_ref().into()),
+        }
+    }
+}
+
+// Returns whether the given piece of normalized text is an operator.
+fn is_operator(piece: &str) -> bool {
+    !piece.is_empty() && piece.chars().all(|c| "+-*/<>=~!@#%^&|".contains(c))
+}
+
+// Returns whether the given piece is a keyword followed by an operand (ex: `and` in `a and -1`).
+//
+// Keywords that can also be column names (ex: `date`) are not included: `date - 1` is a subtraction.
+fn is_operand_keyword(piece: &str) -> bool {
+    OPERAND_KEYWORDS.iter().any(|keyword| piece.eq_ignore_ascii_case(keyword))
+}
+
+// Returns whether the given tokens are a comma separated list of (optionally signed) constants or parameter markers.
+fn is_constant_list(tokens: &[Token]) -> bool {
+    let mut expect_value = true;
+    for token in tokens.iter().filter(|t| !t.is_comment()) {
+        if expect_value {
+            if matches!(token.value, TokenValue::Operator("-") | TokenValue::Operator("+")) {
+                continue;
+            }
+            if !(token.is_numeric_constant() || is_string_constant(token) || token.is_parameter_marker()) {
+                return false;
+            }
+        } else if !token.is_comma() {
+            return false;
+        }
+        expect_value = !expect_value;
+    }
+    // The list must not be empty and must not end with a comma.
+    !expect_value
+}
+
+// Returns whether a space is needed between two pieces of normalized text.
+fn needs_space(previous: &str, next: &str) -> bool {
+    let is_function_call =
+        next == "(" && previous.starts_with(|c: char| c.is_alphabetic() || c == '_') && !is_keyword(previous);
+    !(matches!(previous, "(" | "." | "::") || matches!(next, ")" | "," | "." | "::") || is_function_call)
+}
+
+// The 64-bit FNV-1a hash function.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, &byte| (hash ^ byte as u64).wrapping_mul(0x100000001b3))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::loose_sqlparse;
+
+    fn normalized(sql: &str) -> String {
+        loose_sqlparse(sql).next().unwrap().fingerprint().normalized
+    }
+
+    #[test]
+    fn test_fingerprint_normalization() {
+        assert_eq!(
+            normalized("SELECT a, COUNT(*) FROM t /* c */ WHERE b = 'x' AND c > 1.5 GROUP BY a;"),
+            "select a, COUNT(*) from t where b = ? and c > ? group by a"
+        );
+        assert_eq!(normalized("select \"Col\", `col` from s.t"), "select \"Col\", `col` from s.t");
+        assert_eq!(normalized("SELECT E'x', $$y$$, N'z', x'1F'"), "select ?, ?, ?, ?");
+        assert_eq!(
+            normalized("SELECT a FROM t WHERE id = $1 OR id = :id OR id = ?"),
+            "select a from t where id = ? or id = ? or id = ?"
+        );
+        assert_eq!(normalized("SELECT @@version, '1'::int"), "select @@version, ?::int");
+        assert_eq!(normalized("SELECT a - 1, -1, (-2), a * -3"), "select a - ?, ?, (?), a * ?");
+        assert_eq!(normalized("SELECT date - 1, year + 1 FROM t"), "select date - ?, year + ? from t");
+        assert_eq!(
+            normalized("SELECT 1 WHERE a = 1 AND b > -1 OR c BETWEEN -1 AND +1"),
+            "select ? where a = ? and b > ? or c between ? and ?"
+        );
+        assert_eq!(normalized("SELECT * FROM t WHERE a IN (1, -2, 'x', ?)"), "select * from t where a in (...)");
+        assert_eq!(normalized("SELECT * FROM t WHERE a IN (SELECT 1)"), "select * from t where a in (select ?)");
+        assert_eq!(normalized("SELECT * FROM t WHERE a IN (b, c)"), "select * from t where a in (b, c)");
+        assert_eq!(normalized("INSERT INTO t VALUES (1, 2)"), "insert into t values (?, ?)");
+    }
+
+    #[test]
+    fn test_fingerprint_hash() {
+        let fingerprint = |sql: &str| loose_sqlparse(sql).next().unwrap().fingerprint();
+        assert_eq!(fingerprint("SELECT 1").hash, fingerprint("select  2 -- two").hash);
+        assert_eq!(
+            fingerprint("SELECT * FROM t WHERE a IN (1)").hash,
+            fingerprint("SELECT * FROM t WHERE a IN (1,2)").hash
+        );
+        assert_ne!(fingerprint("SELECT a FROM t").hash, fingerprint("SELECT b FROM t").hash);
+        // The hash must be stable across versions and platforms.
+        assert_eq!(fingerprint("SELECT 1").hash, 0x02fb7a5a1a5a9a58);
+        assert_eq!(super::fnv1a(b""), 0xcbf29ce484222325);
+        assert_eq!(super::fnv1a(b"a"), 0xaf63dc4c8601ec8c);
+    }
+}
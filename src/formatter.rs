@@ -0,0 +1,533 @@
+use crate::clause::split_clauses;
+use crate::keywords::is_keyword;
+use crate::tokens::next_significant;
+use crate::{loose_sqlparse_with_options, ClauseKind, Options, Statement, Token, TokenValue};
+use std::borrow::Cow;
+
+/// A letter case.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Case {
+    /// `SELECT`
+    Upper,
+    /// `select`
+    Lower,
+    /// The case of the original SQL is kept.
+    Preserve,
+}
+
+impl Case {
+    // Applies the case to the given text.
+    pub(crate) fn apply<'t>(&self, text: &'t str) -> Cow<'t, str> {
+        match self {
+            Case::Upper => text.to_uppercase().into(),
+            Case::Lower => text.to_lowercase().into(),
+            Case::Preserve => text.into(),
+        }
+    }
+}
+
+/// The placement of the commas separating the items of a list split over several lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommaPosition {
+    /// The comma ends the line of the previous item.
+    Trailing,
+    /// The comma starts the line of the next item.
+    Leading,
+}
+
+/// Options of [`Statement::format`], [`format()`] and [`format_with_options`].
+#[derive(Debug, Clone)]
+pub struct FormatOptions {
+    /// The text used for one level of indentation.
+    /// The default is two spaces.
+    pub indent: String,
+
+    /// The case of the keywords.
+    /// The default is [`Case::Upper`].
+    pub keyword_case: Case,
+
+    /// The maximum width of a line, longer lines are split when possible.
+    /// The default is 80.
+    pub max_line_width: usize,
+
+    /// The placement of the commas of the lists split over several lines.
+    /// The default is [`CommaPosition::Trailing`].
+    pub comma_position: CommaPosition,
+
+    /// Whether each clause (`FROM`, `WHERE`, ...) starts on a new line.
+    /// The default is `true`.
+    pub newline_before_clause: bool,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            indent: "  ".to_string(),
+            keyword_case: Case::Upper,
+            max_line_width: 80,
+            comma_position: CommaPosition::Trailing,
+            newline_before_clause: true,
+        }
+    }
+}
+
+/// Formats all the statements of the given SQL, see [`Statement::format`].
+///
+/// The formatted statements are separated by a new line.
+///
+/// # Examples
+///
+/// ```rust
+/// use loose_sqlparser::{format, FormatOptions};
+/// assert_eq!(format("select 1; select 2", &FormatOptions::default()), "SELECT 1;\nSELECT 2");
+/// ```
+pub fn format(sql: &str, options: &FormatOptions) -> String {
+    format_with_options(sql, options, Options::default())
+}
+
+/// Formats all the statements of the given SQL parsed with the given options, see [`format()`].
+///
+/// # Examples
+///
+/// ```rust
+/// use loose_sqlparser::{format_with_options, FormatOptions, Options};
+/// let options = Options { psql: true, ..Default::default() };
+/// assert_eq!(
+///     format_with_options("\\connect db\nselect :'name'", &FormatOptions::default(), options),
+///     "\\connect db\nSELECT :'name'"
+/// );
+/// ```
+pub fn format_with_options(sql: &str, format_options: &FormatOptions, options: Options) -> String {
+    loose_sqlparse_with_options(sql, options).map(|stmt| stmt.format(format_options)).collect::<Vec<_>>().join("\n")
+}
+
+impl Statement<'_> {
+    /// Returns the formatted SQL of the statement.
+    ///
+    /// The formatting only relies on the tokens of the statement, so it never fails, even on invalid SQL:
+    /// - each clause starts on a new line (see [`FormatOptions::newline_before_clause`]),
+    /// - lists of a clause (ex: the items of a `SELECT`) too long to fit on a line are split with one item per line,
+    /// - sub-queries and parenthesized expressions too long to fit on a line are indented as blocks,
+    /// - keywords are converted to the requested case, identifiers are left untouched,
+    /// - comments are preserved, a line comment is always followed by a new line,
+    /// - constants, quoted identifiers and other tokens (ex: dollar-quoted bodies) are kept verbatim.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use loose_sqlparser::{loose_sqlparse, FormatOptions};
+    /// let stmt = loose_sqlparse("select a, b from t where a in (select x from u) order by a;").next().unwrap();
+    /// assert_eq!(
+    ///     stmt.format(&FormatOptions::default()),
+    ///     "SELECT a, b\nFROM t\nWHERE a IN (\n  SELECT x\n  FROM u\n)\nORDER BY a;"
+    /// );
+    /// ```
+    pub fn format(&self, options: &FormatOptions) -> String {
+        let mut formatter = Formatter::new(options);
+        formatter.statement(&self.leading);
+        formatter.statement(&self.tokens);
+        let len = formatter.output.trim_end().len();
+        formatter.output.truncate(len);
+        formatter.output
+    }
+}
+
+// The spacing category of the last token written by the formatter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Previous {
+    // The start of a line.
+    LineStart,
+    // `(`
+    Open,
+    // `.` or `::`
+    Joiner,
+    // A `+` or `-` sign (ex: `-1`).
+    Sign,
+    // `,`
+    Comma,
+    // Any other punctuation (ex: `[`), spaced as in the original SQL.
+    Punctuation,
+    // A keyword.
+    Keyword,
+    // An identifier (not a keyword).
+    Identifier,
+    // An operator.
+    Operator,
+    // Any other token (constants, quoted identifiers, parameter markers, comments, ...).
+    Word,
+}
+
+struct Formatter<'o> {
+    options: &'o FormatOptions,
+    output: String,
+    // The width of the current line.
+    column: usize,
+    previous: Previous,
+    // The offset of the end of the last written token in the original SQL.
+    previous_end: usize,
+    // Set after a line comment: the next token must start on a new line.
+    pending_newline: bool,
+}
+
+impl<'o> Formatter<'o> {
+    fn new(options: &'o FormatOptions) -> Self {
+        Self {
+            options,
+            output: String::new(),
+            column: 0,
+            previous: Previous::LineStart,
+            previous_end: 0,
+            pending_newline: false,
+        }
+    }
+
+    fn statement(&mut self, tokens: &[Token]) {
+        let clauses = split_clauses(tokens);
+        let (start, end) = match (clauses.first(), clauses.last()) {
+            (Some(first), Some(last)) => (first.1.start, last.1.end),
+            _ => (tokens.len(), tokens.len()),
+        };
+        // Leading comments are written on their own line.
+        for token in &tokens[..start] {
+            self.write_token(token, None, 0);
+            self.pending_newline |= token.is_comment();
+        }
+        self.clauses(tokens, &clauses, 0);
+        self.write_tokens(&tokens[end..], 0);
+    }
+
+    fn clauses(&mut self, tokens: &[Token], clauses: &[(ClauseKind, std::ops::Range<usize>)], depth: usize) {
+        for (i, (kind, range)) in clauses.iter().enumerate() {
+            if i > 0 && self.options.newline_before_clause {
+                self.newline(depth);
+            }
+            self.clause(*kind, &tokens[range.clone()], depth);
+        }
+    }
+
+    fn clause(&mut self, kind: ClauseKind, tokens: &[Token], depth: usize) {
+        if self.fits(tokens) {
+            self.write_tokens(tokens, depth);
+            return;
+        }
+        match kind {
+            ClauseKind::Select
+            | ClauseKind::From
+            | ClauseKind::GroupBy
+            | ClauseKind::OrderBy
+            | ClauseKind::Set
+            | ClauseKind::Values
+            | ClauseKind::Returning
+            | ClauseKind::Window
+                if tokens.iter().any(Token::is_comma) =>
+            {
+                let items = list_start(kind, tokens);
+                self.write_tokens(&tokens[..items], depth);
+                self.newline(depth + 1);
+                self.list(&tokens[items..], depth + 1);
+            }
+            ClauseKind::Where | ClauseKind::Having => {
+                // Long conditions are split before each top-level `AND` / `OR`.
+                let mut start = 0;
+                for (i, token) in tokens.iter().enumerate() {
+                    if i > 1 && (token.is_keyword("AND") || token.is_keyword("OR")) && !is_between_and(tokens, i) {
+                        self.write_tokens(&tokens[start..i], depth);
+                        self.newline(depth + 1);
+                        start = i;
+                    }
+                }
+                self.write_tokens(&tokens[start..], depth);
+            }
+            _ => self.write_tokens(tokens, depth),
+        }
+    }
+
+    // Writes a comma separated list with one item per line, the current line being the line of the first item.
+    fn list(&mut self, tokens: &[Token], depth: usize) {
+        for (i, token) in tokens.iter().enumerate() {
+            if token.is_comma() {
+                // A comma cannot follow a line comment on the same line.
+                match self.options.comma_position {
+                    CommaPosition::Trailing if !self.pending_newline => {
+                        self.write_token(token, None, depth);
+                        self.newline(depth);
+                    }
+                    _ => {
+                        self.newline(depth);
+                        self.write_token(token, None, depth);
+                    }
+                }
+            } else {
+                self.write_tokens(&tokens[i..i + 1], depth);
+            }
+        }
+    }
+
+    // Writes the content of a fragment as a block indented at `depth`.
+    fn block(&mut self, tokens: &[Token], depth: usize) {
+        self.newline(depth);
+        let clauses = split_clauses(tokens);
+        let is_query = clauses.first().is_some_and(|(kind, _)| *kind != ClauseKind::Other);
+        if is_query {
+            let start = clauses[0].1.start;
+            self.write_tokens(&tokens[..start], depth);
+            self.clauses(tokens, &clauses, depth);
+            self.write_tokens(&tokens[clauses[clauses.len() - 1].1.end..], depth);
+        } else if tokens.iter().any(Token::is_comma) {
+            self.list(tokens, depth);
+        } else {
+            self.write_tokens(tokens, depth);
+        }
+    }
+
+    fn write_tokens(&mut self, tokens: &[Token], depth: usize) {
+        for (i, token) in tokens.iter().enumerate() {
+            match &token.value {
+                TokenValue::Fragment(children, _) => {
+                    if children.is_empty() {
+                        continue;
+                    }
+                    let is_query = next_significant(children, 0)
+                        .is_some_and(|i| children[i].is_keyword("SELECT") || children[i].is_keyword("WITH"));
+                    if is_query || !self.fits(children) {
+                        self.block(children, depth + 1);
+                        self.newline(depth);
+                    } else {
+                        self.write_tokens(children, depth);
+                    }
+                }
+                _ => self.write_token(token, tokens.get(i + 1), depth),
+            }
+        }
+    }
+
+    fn write_token(&mut self, token: &Token, next: Option<&Token>, depth: usize) {
+        if let TokenValue::CopyData(data) = token.value {
+            // The data of `COPY ... FROM STDIN` is written as is, starting on the line following the statement.
+            self.newline(0);
+            self.output.push_str(data);
+            self.pending_newline = true;
+            return;
+        }
+        if self.pending_newline {
+            self.newline(depth);
+        }
+        let value = token.value.as_ref();
+        let is_qualified = self.previous == Previous::Joiner && self.output.ends_with('.')
+            || next.is_some_and(|t| matches!(t.value, TokenValue::Any(".")));
+        let text = match token.value {
+            TokenValue::IdentifierOrKeyword(_) if is_keyword(value) && !is_qualified => {
+                self.options.keyword_case.apply(value)
+            }
+            _ => value.into(),
+        };
+
+        let adjacent = token.start.offset == self.previous_end;
+        let space = match (self.previous, &token.value) {
+            (Previous::LineStart | Previous::Open | Previous::Joiner | Previous::Sign, _) => false,
+            (_, TokenValue::StatementDelimiter(_)) => false,
+            (_, TokenValue::Any(")" | "," | ".") | TokenValue::Operator("::")) => false,
+            // Function calls (ex: `count(*)`) or keywords (ex: `IN (...)`) keep the original spacing.
+            (Previous::Identifier | Previous::Keyword, TokenValue::Any("(")) => !adjacent,
+            (_, TokenValue::Any("(")) => true,
+            (Previous::Punctuation, _) | (_, TokenValue::Any(_)) => !adjacent,
+            _ => true,
+        };
+        // A sign followed by `-` (ex: `- -1`) or a `/` followed by `*` must stay apart to not start a comment.
+        let space = space
+            || (self.output.ends_with(['-', '+']) && text.starts_with('-'))
+            || (self.output.ends_with('/') && text.starts_with('*'));
+        if space {
+            self.output.push(' ');
+            self.column += 1;
+        }
+        self.output.push_str(&text);
+        match text.rfind('\n') {
+            Some(newline) => self.column = text[newline + 1..].chars().count(),
+            None => self.column += text.chars().count(),
+        }
+
+        let is_sign = matches!(value, "-" | "+")
+            && matches!(
+                self.previous,
+                Previous::LineStart
+                    | Previous::Open
+                    | Previous::Comma
+                    | Previous::Keyword
+                    | Previous::Operator
+                    | Previous::Sign
+            );
+        self.previous = match &token.value {
+            TokenValue::Any("(") => Previous::Open,
+            TokenValue::Any(",") => Previous::Comma,
+            TokenValue::Any(")") => Previous::Word,
+            TokenValue::Any(".") | TokenValue::Operator("::") => Previous::Joiner,
+            TokenValue::Any(_) => Previous::Punctuation,
+            TokenValue::Operator(_) if is_sign => Previous::Sign,
+            TokenValue::Operator(_) => Previous::Operator,
+            TokenValue::IdentifierOrKeyword(_) if is_keyword(value) && !is_qualified => Previous::Keyword,
+            TokenValue::IdentifierOrKeyword(_) => Previous::Identifier,
+            _ => Previous::Word,
+        };
+        self.previous_end = token.end.offset;
+        self.pending_newline = token.is_comment() && !value.starts_with("/*");
+    }
+
+    fn newline(&mut self, depth: usize) {
+        let len = self.output.trim_end_matches([' ', '\t']).len();
+        self.output.truncate(len);
+        if !self.output.is_empty() {
+            self.output.push('\n');
+        }
+        for _ in 0..depth {
+            self.output.push_str(&self.options.indent);
+        }
+        self.column = self.options.indent.chars().count() * depth;
+        self.previous = Previous::LineStart;
+        self.pending_newline = false;
+    }
+
+    // Returns whether the given tokens fit on the current line.
+    fn fits(&self, tokens: &[Token]) -> bool {
+        inline_width(tokens).is_some_and(|width| self.column + 1 + width <= self.options.max_line_width)
+    }
+}
+
+// Returns the width of the given tokens written on a single line, or `None` if they cannot be written on a single line
+// (line comments, multi-line tokens or sub-queries).
+fn inline_width(tokens: &[Token]) -> Option<usize> {
+    let mut width = 0;
+    for token in tokens {
+        match &token.value {
+            TokenValue::Fragment(children, _) => {
+                if next_significant(children, 0)
+                    .is_some_and(|i| children[i].is_keyword("SELECT") || children[i].is_keyword("WITH"))
+                {
+                    return None;
+                }
+                width += inline_width(children)?;
+            }
+            TokenValue::Comment(comment) if !comment.starts_with("/*") => return None,
+            value if value.as_ref().contains('\n') => return None,
+            TokenValue::Any("(" | ")" | "," | ".") | TokenValue::Operator("::") => width += 1,
+            value => width += value.as_ref().chars().count() + 1,
+        }
+    }
+    Some(width)
+}
+
+// Returns the index of the first item of the list of a clause (ex: the first expression of a `SELECT`).
+fn list_start(kind: ClauseKind, tokens: &[Token]) -> usize {
+    let mut index = next_significant(tokens, 0).map_or(0, |i| i + 1);
+    if kind == ClauseKind::GroupBy || kind == ClauseKind::OrderBy {
+        index = next_significant(tokens, index).map_or(index, |i| i + 1);
+    }
+    if kind == ClauseKind::Select {
+        if let Some(i) =
+            next_significant(tokens, index).filter(|&i| tokens[i].is_keyword("DISTINCT") || tokens[i].is_keyword("ALL"))
+        {
+            index = i + 1;
+        }
+    }
+    index.min(tokens.len())
+}
+
+// Returns whether the `AND` at the given index is part of a `BETWEEN ... AND ...` expression.
+fn is_between_and(tokens: &[Token], index: usize) -> bool {
+    tokens[..index]
+        .iter()
+        .rev()
+        .filter(|t| t.is_identifier_or_keyword())
+        .find(|t| t.is_keyword("AND") || t.is_keyword("OR") || t.is_keyword("BETWEEN") || t.is_keyword("WHERE"))
+        .is_some_and(|t| t.is_keyword("BETWEEN"))
+        && tokens[index].is_keyword("AND")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{format, format_with_options, loose_sqlparse, Case, CommaPosition, FormatOptions, Options};
+
+    #[test]
+    fn test_format_clauses() {
+        let options = FormatOptions::default();
+        assert_eq!(
+            format("select count(*), t.type, -1, a - -2, '1'::int, arr[1] from t where x between 1 and 2", &options),
+            "SELECT count(*), t.type, -1, a - -2, '1'::INT, arr[1]\nFROM t\nWHERE x BETWEEN 1 AND 2"
+        );
+        assert_eq!(
+            format(
+                "INSERT INTO t (a, b) VALUES (1, 2), (3, 4) ON CONFLICT (a) DO UPDATE SET b = 1 RETURNING *",
+                &options
+            ),
+            "INSERT INTO t (a, b)\nVALUES (1, 2), (3, 4)\nON CONFLICT (a) DO UPDATE SET b = 1\nRETURNING *"
+        );
+        assert_eq!(
+            format(
+                "with x as (select 1) select very_long_column_name_one, very_long_column_name_two, \
+                 very_long_column_name_three from x where x.aaaaaaaaaaaaaaaaaaaaaaaaaaaaa = 1 and \
+                 x.bbbbbbbbbbbbbbbbbbbbbbbbbbb = 2 and z between 1 and 2",
+                &options
+            ),
+            "WITH x AS (\n  SELECT 1\n)\nSELECT\n  very_long_column_name_one,\n  very_long_column_name_two,\n  \
+             very_long_column_name_three\nFROM x\nWHERE x.aaaaaaaaaaaaaaaaaaaaaaaaaaaaa = 1\n  \
+             AND x.bbbbbbbbbbbbbbbbbbbbbbbbbbb = 2\n  AND z BETWEEN 1 AND 2"
+        );
+    }
+
+    #[test]
+    fn test_format_options() {
+        let options = FormatOptions {
+            indent: "    ".to_string(),
+            keyword_case: Case::Lower,
+            max_line_width: 24,
+            comma_position: CommaPosition::Leading,
+            newline_before_clause: false,
+        };
+        assert_eq!(
+            format("SELECT aaaaaa, bbbbbbb FROM t WHERE f(aaaaaaaa, bbbbbbbbb, ccccccccc)", &options),
+            "select aaaaaa, bbbbbbb from t where f(\n    aaaaaaaa\n    , bbbbbbbbb\n    , ccccccccc\n)"
+        );
+        let options =
+            FormatOptions { keyword_case: Case::Preserve, newline_before_clause: false, ..Default::default() };
+        assert_eq!(format("Select  a\nFROM t", &options), "Select a FROM t");
+    }
+
+    #[test]
+    fn test_format_preserves_tokens() {
+        let options = FormatOptions::default();
+        assert_eq!(
+            format("/* lead */ -- line\nselect 1 -- one\n, \"Select\" from t;", &options),
+            "/* lead */\n-- line\nSELECT\n  1 -- one\n  , \"Select\"\nFROM t;"
+        );
+        assert_eq!(
+            format("create function f() returns int as $$\nbegin\n  return 1;\nend\n$$ language plpgsql", &options),
+            "CREATE FUNCTION f() RETURNS INT AS $$\nbegin\n  return 1;\nend\n$$ LANGUAGE plpgsql"
+        );
+        assert_eq!(
+            format("SELECT @@session.sql_mode, :name, $1, ?", &options),
+            "SELECT @@session.sql_mode, :name, $1, ?"
+        );
+        // Signs are not merged with the following token into a comment.
+        let sql = "SELECT a FROM t WHERE b = - -1 AND c = + -2 AND d = -(-3)";
+        let formatted = format(sql, &options);
+        assert_eq!(formatted, "SELECT a\nFROM t\nWHERE b = - -1 AND c = + -2 AND d = -(-3)");
+        let tokens = |sql: &str| loose_sqlparse(sql).next().unwrap().tokens().as_str_array().join(" ");
+        assert_eq!(tokens(&formatted), tokens(sql));
+        // Invalid SQL is formatted too.
+        assert_eq!(format("select ((( from where 'unterminated", &options), "SELECT (((FROM WHERE 'unterminated");
+        assert_eq!(format("", &options), "");
+    }
+
+    #[test]
+    fn test_format_psql() {
+        let options = Options { psql: true, ..Default::default() };
+        assert_eq!(
+            format_with_options(
+                "select a from t where b = :'b' \\gexec\n\\set x 1",
+                &FormatOptions::default(),
+                options
+            ),
+            "SELECT a\nFROM t\nWHERE b = :'b'\n\\gexec\n\\set x 1"
+        );
+    }
+}
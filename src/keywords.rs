@@ -0,0 +1,63 @@
+// The list of SQL keywords recognized by the helper functions interpreting the tokens of a statement.
+//
+// The tokenizer does not distinguish keywords from identifiers ([`crate::TokenValue::IdentifierOrKeyword`]), this list
+// is used when a distinction is needed (ex: to change the case of keywords). It's a union of the reserved and most
+// common non-reserved keywords of the main SQL dialects, including the names of the built-in data types.
+//
+// IMPORTANT: The list must be sorted in ascending order and in uppercase (binary search).
+#[rustfmt::skip]
+const KEYWORDS: [&str; 257] = [
+    "ABORT", "ACTION", "ADD", "AFTER", "ALL", "ALTER", "ALWAYS", "ANALYZE", "AND", "ANY", "ARRAY", "AS", "ASC",
+    "ATTACH", "AUTHORIZATION", "AUTOINCREMENT", "AUTO_INCREMENT", "BEFORE", "BEGIN", "BETWEEN", "BIGINT", "BINARY",
+    "BLOB", "BOOLEAN", "BOTH", "BY", "CALL", "CASCADE", "CASE", "CAST", "CHAR", "CHARACTER", "CHECK", "CLUSTER",
+    "COLLATE", "COLUMN", "COMMENT", "COMMIT", "CONCURRENTLY", "CONFLICT", "CONSTRAINT", "COPY", "CREATE", "CROSS",
+    "CUBE", "CURRENT", "CURRENT_DATE", "CURRENT_TIME", "CURRENT_TIMESTAMP", "CURRENT_USER", "CURSOR", "CYCLE",
+    "DATABASE", "DATE", "DATETIME", "DEALLOCATE", "DECIMAL", "DECLARE", "DEFAULT", "DEFERRABLE", "DEFERRED", "DELETE",
+    "DELIMITER", "DESC", "DESCRIBE", "DETACH", "DISTINCT", "DO", "DOUBLE", "DROP", "DUPLICATE", "EACH", "ELSE", "ELSIF",
+    "END", "ENUM", "ESCAPE", "EXCEPT", "EXCLUDE", "EXCLUSIVE", "EXEC", "EXECUTE", "EXISTS", "EXPLAIN", "EXTENSION",
+    "FALSE", "FETCH", "FILTER", "FIRST", "FLOAT", "FOLLOWING", "FOR", "FOREIGN", "FROM", "FULL", "FUNCTION",
+    "GENERATED", "GLOBAL", "GRANT", "GROUP", "GROUPING", "HAVING", "IF", "IGNORE", "ILIKE", "IMMEDIATE", "IN", "INDEX",
+    "INNER", "INOUT", "INSERT", "INSTEAD", "INT", "INTEGER", "INTERSECT", "INTERVAL", "INTO", "IS", "ISNULL", "JOIN",
+    "JSON", "JSONB", "KEY", "LANGUAGE", "LAST", "LATERAL", "LEADING", "LEFT", "LIKE", "LIMIT", "LISTEN", "LOCAL",
+    "LOCK", "LOOP", "MATCH", "MATCHED", "MATERIALIZED", "MERGE", "MINUS", "NATURAL", "NCHAR", "NEXT", "NO", "NOT",
+    "NOTHING", "NOTIFY", "NOTNULL", "NOWAIT", "NULL", "NULLS", "NUMERIC", "NVARCHAR", "OF", "OFFSET", "ON", "ONLY",
+    "OR", "ORDER", "OTHERS", "OUT", "OUTER", "OVER", "OVERLAPS", "OWNER", "PARTITION", "PERCENT", "PLACING", "PRAGMA",
+    "PRECEDING", "PRECISION", "PREPARE", "PRIMARY", "PRIVILEGES", "PROCEDURE", "QUALIFY", "RANGE", "REAL", "RECURSIVE",
+    "REFERENCES", "REFRESH", "REINDEX", "RELEASE", "RENAME", "REPLACE", "RESTRICT", "RETURN", "RETURNING", "RETURNS",
+    "REVOKE", "RIGHT", "ROLE", "ROLLBACK", "ROLLUP", "ROW", "ROWS", "SAVEPOINT", "SCHEMA", "SEARCH", "SELECT",
+    "SEQUENCE", "SERIAL", "SESSION", "SESSION_USER", "SET", "SETS", "SHARE", "SHOW", "SIMILAR", "SKIP", "SMALLINT",
+    "SOME", "START", "STRAIGHT_JOIN", "SYMMETRIC", "TABLE", "TABLESAMPLE", "TEMP", "TEMPORARY", "TEXT", "THEN", "TIES",
+    "TIME", "TIMESTAMP", "TINYINT", "TO", "TOP", "TRAILING", "TRANSACTION", "TRIGGER", "TRUE", "TRUNCATE", "TYPE",
+    "UNBOUNDED", "UNION", "UNIQUE", "UNKNOWN", "UNLISTEN", "UNLOGGED", "UNSIGNED", "UPDATE", "USE", "USING", "VACUUM",
+    "VALUES", "VARCHAR", "VARIADIC", "VARYING", "VIEW", "VIRTUAL", "WHEN", "WHERE", "WHILE", "WINDOW", "WITH", "WITHIN",
+    "WITHOUT", "ZONE",
+];
+
+// Returns whether the given word is a SQL keyword (case-insensitive).
+pub(crate) fn is_keyword(word: &str) -> bool {
+    if word.len() > 32 || !word.is_ascii() {
+        return false;
+    }
+    KEYWORDS.binary_search(&word.to_ascii_uppercase().as_str()).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keywords_are_sorted() {
+        assert!(KEYWORDS.windows(2).all(|w| w[0] < w[1]), "KEYWORDS must be sorted");
+        assert!(KEYWORDS.iter().all(|k| k.chars().all(|c| c.is_ascii_uppercase() || c == '_')));
+    }
+
+    #[test]
+    fn test_is_keyword() {
+        assert!(is_keyword("SELECT"));
+        assert!(is_keyword("select"));
+        assert!(is_keyword("Current_Timestamp"));
+        assert!(!is_keyword("users"));
+        assert!(!is_keyword("sélect"));
+        assert!(!is_keyword(""));
+    }
+}
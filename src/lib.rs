@@ -3,15 +3,48 @@
 #[cfg(feature = "serialize")]
 use serde::Serialize;
 
+mod clause;
+mod comment;
+mod cte;
+mod error;
+mod fingerprint;
+mod formatter;
+mod keywords;
+mod limit;
+pub mod migrations;
+mod minify;
+mod named_query;
 mod options;
+mod parameter;
+mod recase;
+mod redact;
+mod rewriter;
+mod select;
 mod statement;
 mod tokenizer;
 mod tokens;
 
 // Re-export the public API
-pub use options::Options;
+pub use clause::{Clause, ClauseKind};
+pub use comment::{Comment, CommentKind, CommentPlacement};
+pub use cte::{Cte, WithClause};
+pub use error::Error;
+pub use fingerprint::Fingerprint;
+pub use formatter::{format, format_with_options, Case, CommaPosition, FormatOptions};
+pub use migrations::{
+    parse_flyway_file_name, parse_migration, parse_migration_with_options, FlywayFileName, FlywayKind, Migration,
+    MigrationFormat,
+};
+pub use minify::{minify, minify_with_options, MinifyOptions};
+pub use named_query::{named_queries, named_queries_with_options, NamedQueries, NamedQuery};
+pub use options::{Dialect, Options, QuestionMarkMode};
+pub use parameter::{Parameter, ParameterStyle, RewrittenParameters};
+pub use recase::CaseOptions;
+pub use redact::RedactOptions;
+pub use rewriter::{EditSet, OffsetMap, Rewritten};
+pub use select::{SelectAlias, SelectItem};
 pub use statement::Statement;
-pub use tokens::{Token, TokenValue, Tokens};
+pub use tokens::{FragmentKind, Token, TokenValue, Tokens, VariableScope};
 
 use tokenizer::Tokenizer;
 
@@ -101,12 +134,12 @@ mod tests {
         assert_eq!(statements[0].tokens().as_str_array(), ["SELECT", "/* one */", "1", ";"]);
         assert_eq!(statements[1].tokens().as_str_array(), ["SELECT", "2"]);
 
-        let options = Options { statement_delimiter: "\\".to_string() };
+        let options = Options { statement_delimiter: "\\".to_string(), ..Default::default() };
         let statements: Vec<_> = loose_sqlparse_with_options("SELECT /* one */ 1\\SELECT 2", options).collect();
         assert_eq!(statements[0].tokens().as_str_array(), ["SELECT", "/* one */", "1", "\\"]);
         assert_eq!(statements[1].tokens().as_str_array(), ["SELECT", "2"]);
 
-        let options = Options { statement_delimiter: "\\".to_string() };
+        let options = Options { statement_delimiter: "\\".to_string(), ..Default::default() };
         let statements: Vec<_> = parse_with_options("SELECT /* one */ 1\\SELECT 2", options).collect();
         assert_eq!(statements[0].tokens().as_str_array(), ["SELECT", "/* one */", "1", "\\"]);
         assert_eq!(statements[1].tokens().as_str_array(), ["SELECT", "2"]);
@@ -122,7 +155,7 @@ mod tests {
         let json = serde_json::to_string(&statements).unwrap();
         assert_eq!(
             json,
-            r#"[{"input":"SELECT /* one */ 1; SELECT (2+1)","tokens":[{"type":"IdentifierOrKeyword","value":"SELECT","start":{"line":1,"column":1,"offset":0},"end":{"line":1,"column":6,"offset":6}},{"type":"Comment","value":"/* one */","start":{"line":1,"column":8,"offset":7},"end":{"line":1,"column":16,"offset":16}},{"type":"NumericConstant","value":"1","start":{"line":1,"column":18,"offset":17},"end":{"line":1,"column":18,"offset":18}},{"type":"StatementDelimiter","value":";","start":{"line":1,"column":19,"offset":18},"end":{"line":1,"column":19,"offset":19}}]},{"input":"SELECT /* one */ 1; SELECT (2+1)","tokens":[{"type":"IdentifierOrKeyword","value":"SELECT","start":{"line":1,"column":21,"offset":20},"end":{"line":1,"column":26,"offset":26}},{"type":"Any","value":"(","start":{"line":1,"column":28,"offset":27},"end":{"line":1,"column":28,"offset":28}},{"type":"Fragment","value":[{"type":"NumericConstant","value":"2","start":{"line":1,"column":29,"offset":28},"end":{"line":1,"column":29,"offset":29}},{"type":"Operator","value":"+","start":{"line":1,"column":30,"offset":29},"end":{"line":1,"column":30,"offset":30}},{"type":"NumericConstant","value":"1","start":{"line":1,"column":31,"offset":30},"end":{"line":1,"column":31,"offset":31}}],"start":{"line":1,"column":32,"offset":31},"end":{"line":1,"column":31,"offset":31}},{"type":"Any","value":")","start":{"line":1,"column":32,"offset":31},"end":{"line":1,"column":32,"offset":32}}]}]"#
+            r#"[{"input":"SELECT /* one */ 1; SELECT (2+1)","tokens":[{"type":"IdentifierOrKeyword","value":"SELECT","start":{"line":1,"column":1,"offset":0},"end":{"line":1,"column":6,"offset":6}},{"type":"Comment","value":"/* one */","start":{"line":1,"column":8,"offset":7},"end":{"line":1,"column":16,"offset":16}},{"type":"NumericConstant","value":"1","start":{"line":1,"column":18,"offset":17},"end":{"line":1,"column":18,"offset":18}},{"type":"StatementDelimiter","value":";","start":{"line":1,"column":19,"offset":18},"end":{"line":1,"column":19,"offset":19}}]},{"input":"SELECT /* one */ 1; SELECT (2+1)","tokens":[{"type":"IdentifierOrKeyword","value":"SELECT","start":{"line":1,"column":21,"offset":20},"end":{"line":1,"column":26,"offset":26}},{"type":"Any","value":"(","start":{"line":1,"column":28,"offset":27},"end":{"line":1,"column":28,"offset":28}},{"type":"Fragment","value":[{"type":"NumericConstant","value":"2","start":{"line":1,"column":29,"offset":28},"end":{"line":1,"column":29,"offset":29}},{"type":"Operator","value":"+","start":{"line":1,"column":30,"offset":29},"end":{"line":1,"column":30,"offset":30}},{"type":"NumericConstant","value":"1","start":{"line":1,"column":31,"offset":30},"end":{"line":1,"column":31,"offset":31}}],"kind":"Parentheses","start":{"line":1,"column":32,"offset":31},"end":{"line":1,"column":31,"offset":31}},{"type":"Any","value":")","start":{"line":1,"column":32,"offset":31},"end":{"line":1,"column":32,"offset":32}}]}]"#
         );
     }
 }
@@ -0,0 +1,166 @@
+use crate::clause::split_clauses;
+use crate::tokens::{leaves, next_significant};
+use crate::{ClauseKind, Dialect, Statement, Token};
+
+impl Statement<'_> {
+    /// Returns the SQL of the statement with a clause limiting the number of rows returned, or `None` if the statement
+    /// doesn't need one.
+    ///
+    /// The limit is only added to queries (`SELECT`, `WITH ... SELECT` and `VALUES`) that don't already have a
+    /// top-level `LIMIT`, `FETCH FIRST` or `TOP` clause (limits of the sub-queries are ignored). The clause depends on
+    /// the dialect:
+    /// - `LIMIT n` for most of the dialects,
+    /// - `FETCH FIRST n ROWS ONLY` for [`Dialect::Oracle`],
+    /// - `TOP n` after `SELECT` for [`Dialect::SqlServer`] (not supported for queries combined with `UNION`,
+    ///   `INTERSECT` or `EXCEPT`, nor for queries with an `OFFSET` clause which cannot be combined with `TOP`).
+    ///
+    /// The `LIMIT` and `FETCH FIRST` clauses are inserted after the last token of the query, before the trailing
+    /// comments and the statement delimiter, or before the locking clause (ex: `FOR UPDATE`, `LOCK IN SHARE MODE`).
+    ///
+    /// The dialect is an argument rather than taken from [`crate::Options::dialect`] because a statement doesn't keep
+    /// the options it was parsed with, and the limit must be written for the database that will run the query, which
+    /// may be more specific than the dialect used to parse it (ex: [`Dialect::Generic`]).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use loose_sqlparser::{loose_sqlparse, Dialect};
+    /// let stmt = loose_sqlparse("SELECT * FROM t -- all rows\n;").next().unwrap();
+    /// let limited = |dialect| stmt.inject_limit(100, dialect).unwrap();
+    /// assert_eq!(limited(Dialect::Generic), "SELECT * FROM t LIMIT 100 -- all rows\n;");
+    /// assert_eq!(limited(Dialect::Oracle), "SELECT * FROM t FETCH FIRST 100 ROWS ONLY -- all rows\n;");
+    /// assert_eq!(limited(Dialect::SqlServer), "SELECT TOP 100 * FROM t -- all rows\n;");
+    ///
+    /// let stmt = loose_sqlparse("SELECT * FROM t LIMIT 10").next().unwrap();
+    /// assert_eq!(stmt.inject_limit(100, Dialect::Generic), None);
+    /// ```
+    pub fn inject_limit(&self, limit: u64, dialect: Dialect) -> Option<String> {
+        if !self.is_query() {
+            return None;
+        }
+        let tokens = &self.tokens;
+        let clauses = split_clauses(tokens);
+        let main = clauses.iter().find(|(kind, _)| *kind != ClauseKind::With)?;
+        if !matches!(main.0, ClauseKind::Select | ClauseKind::Values) {
+            return None;
+        }
+
+        // Look for an existing limit.
+        let mut select_modifier = None;
+        for (kind, range) in &clauses {
+            match kind {
+                ClauseKind::Limit | ClauseKind::Fetch => return None,
+                ClauseKind::Select => {
+                    // `SELECT [ALL | DISTINCT] TOP n ...`
+                    let mut index = next_significant(tokens, range.start + 1).filter(|&i| i < range.end);
+                    if let Some(i) = index.filter(|&i| tokens[i].is_keyword("ALL") || tokens[i].is_keyword("DISTINCT"))
+                    {
+                        select_modifier.get_or_insert(i);
+                        index = next_significant(tokens, i + 1).filter(|&i| i < range.end);
+                    }
+                    if index.is_some_and(|i| tokens[i].is_keyword("TOP")) {
+                        return None;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let mut edits = self.edits();
+        if dialect == Dialect::SqlServer {
+            if main.0 != ClauseKind::Select
+                || clauses.iter().any(|(kind, _)| matches!(kind, ClauseKind::SetOperator | ClauseKind::Offset))
+            {
+                return None;
+            }
+            let after = select_modifier.filter(|&i| i < main.1.end).unwrap_or(main.1.start);
+            edits.insert_after(&tokens[after], format!(" TOP {}", limit)).ok()?;
+        } else {
+            let clause = match dialect {
+                Dialect::Oracle => format!("FETCH FIRST {} ROWS ONLY", limit),
+                _ => format!("LIMIT {}", limit),
+            };
+            if let Some(lock) = locking_clause(tokens) {
+                edits.insert_before(&tokens[lock], format!("{} ", clause)).ok()?;
+            } else {
+                let last = leaves(tokens).into_iter().rev().find(|t| !t.is_comment() && !t.is_statement_delimiter())?;
+                edits.insert_after(last, format!(" {}", clause)).ok()?;
+            }
+        }
+        Some(edits.apply().sql)
+    }
+}
+
+// Returns the index of the first token of the top-level locking clause of a query (`FOR UPDATE`, `FOR NO KEY UPDATE`,
+// `FOR SHARE`, `FOR KEY SHARE` or `LOCK IN SHARE MODE`).
+fn locking_clause(tokens: &[Token]) -> Option<usize> {
+    (0..tokens.len()).find(|&i| {
+        let next = next_significant(tokens, i + 1).map(|next| &tokens[next]);
+        let is_followed_by = |keywords: &[&str]| next.is_some_and(|t| keywords.iter().any(|k| t.is_keyword(k)));
+        (tokens[i].is_keyword("FOR") && is_followed_by(&["UPDATE", "SHARE", "NO", "KEY"]))
+            || (tokens[i].is_keyword("LOCK") && is_followed_by(&["IN"]))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{loose_sqlparse, Dialect};
+
+    fn inject_limit(sql: &str, dialect: Dialect) -> Option<String> {
+        loose_sqlparse(sql).next().unwrap().inject_limit(10, dialect)
+    }
+
+    #[test]
+    fn test_inject_limit() {
+        assert_eq!(
+            inject_limit("SELECT a FROM t WHERE a IN (SELECT b FROM u LIMIT 1) /* c */;", Dialect::PostgreSql),
+            Some("SELECT a FROM t WHERE a IN (SELECT b FROM u LIMIT 1) LIMIT 10 /* c */;".to_string())
+        );
+        assert_eq!(
+            inject_limit("WITH x AS (SELECT 1 LIMIT 1) SELECT * FROM x UNION SELECT 2", Dialect::MySql),
+            Some("WITH x AS (SELECT 1 LIMIT 1) SELECT * FROM x UNION SELECT 2 LIMIT 10".to_string())
+        );
+        assert_eq!(inject_limit("VALUES (1), (2)", Dialect::Sqlite), Some("VALUES (1), (2) LIMIT 10".to_string()));
+        assert_eq!(
+            inject_limit("SELECT DISTINCT a FROM t ORDER BY a", Dialect::SqlServer),
+            Some("SELECT DISTINCT TOP 10 a FROM t ORDER BY a".to_string())
+        );
+        assert_eq!(
+            inject_limit("select * from t order by a", Dialect::Oracle),
+            Some("select * from t order by a FETCH FIRST 10 ROWS ONLY".to_string())
+        );
+    }
+
+    #[test]
+    fn test_inject_limit_locking_clause() {
+        assert_eq!(
+            inject_limit("SELECT * FROM t WHERE a IN (SELECT b FROM u FOR UPDATE) FOR UPDATE;", Dialect::PostgreSql),
+            Some("SELECT * FROM t WHERE a IN (SELECT b FROM u FOR UPDATE) LIMIT 10 FOR UPDATE;".to_string())
+        );
+        assert_eq!(
+            inject_limit("SELECT * FROM t FOR NO KEY UPDATE NOWAIT", Dialect::PostgreSql),
+            Some("SELECT * FROM t LIMIT 10 FOR NO KEY UPDATE NOWAIT".to_string())
+        );
+        assert_eq!(
+            inject_limit("SELECT * FROM t LOCK IN SHARE MODE", Dialect::MySql),
+            Some("SELECT * FROM t LIMIT 10 LOCK IN SHARE MODE".to_string())
+        );
+        assert_eq!(
+            inject_limit("SELECT * FROM t FOR SHARE", Dialect::Oracle),
+            Some("SELECT * FROM t FETCH FIRST 10 ROWS ONLY FOR SHARE".to_string())
+        );
+    }
+
+    #[test]
+    fn test_inject_limit_not_needed() {
+        assert_eq!(inject_limit("SELECT * FROM t LIMIT 5", Dialect::Generic), None);
+        assert_eq!(inject_limit("SELECT * FROM t OFFSET 5 ROWS FETCH NEXT 5 ROWS ONLY", Dialect::Generic), None);
+        assert_eq!(inject_limit("SELECT TOP 5 * FROM t", Dialect::SqlServer), None);
+        assert_eq!(inject_limit("SELECT DISTINCT TOP 5 * FROM t", Dialect::SqlServer), None);
+        assert_eq!(inject_limit("SELECT 1 UNION SELECT 2", Dialect::SqlServer), None);
+        assert_eq!(inject_limit("SELECT * FROM t ORDER BY a OFFSET 5 ROWS", Dialect::SqlServer), None);
+        assert_eq!(inject_limit("SHOW TABLES", Dialect::MySql), None);
+        assert_eq!(inject_limit("INSERT INTO t VALUES (1) RETURNING *", Dialect::PostgreSql), None);
+        assert_eq!(inject_limit("DELETE FROM t", Dialect::Generic), None);
+    }
+}
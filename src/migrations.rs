@@ -0,0 +1,346 @@
+//! Parsing of migration files (dbmate, goose, sql-migrate and Flyway), see [`parse_migration`].
+//!
+//! The items of this module are also re-exported at the root of the crate.
+//!
+//! ```rust
+//! use loose_sqlparser::migrations::{parse_migration, MigrationFormat};
+//! let migration = parse_migration("-- migrate:up\nCREATE TABLE t (id INT);\n-- migrate:down\nDROP TABLE t;\n");
+//! assert_eq!(migration.format, Some(MigrationFormat::Dbmate));
+//! assert_eq!(migration.down[0].sql(), "DROP TABLE t;");
+//! ```
+
+use crate::{loose_sqlparse_with_options, Options, Statement, Token, TokenValue};
+
+/// The format of the directives of a migration file, see [`parse_migration`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MigrationFormat {
+    /// `-- migrate:up` and `-- migrate:down` (dbmate).
+    Dbmate,
+    /// `-- +goose Up`, `-- +goose Down`, `-- +goose StatementBegin` and `-- +goose StatementEnd` (goose).
+    Goose,
+    /// `-- +migrate Up`, `-- +migrate Down`, `-- +migrate StatementBegin` and `-- +migrate StatementEnd`
+    /// (sql-migrate).
+    SqlMigrate,
+}
+
+/// The statements of a migration file, see [`parse_migration`].
+#[derive(Debug)]
+pub struct Migration<'s> {
+    /// The format of the first directive found in the file, `None` if the file has no directives.
+    pub format: Option<MigrationFormat>,
+
+    /// The statements applying the migration.
+    pub up: Vec<Statement<'s>>,
+
+    /// The statements reverting the migration.
+    pub down: Vec<Statement<'s>>,
+
+    /// Whether the migration should run in a transaction.
+    /// This is `false` when the file has a `-- +goose NO TRANSACTION`, `-- migrate:up transaction:false` or
+    /// `-- +migrate Up notransaction` directive.
+    pub transaction: bool,
+}
+
+/// The kind of a Flyway migration, see [`parse_flyway_file_name`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlywayKind {
+    /// A versioned migration (`V<version>__<description>.sql`).
+    Versioned,
+    /// An undo migration (`U<version>__<description>.sql`).
+    Undo,
+    /// A repeatable migration (`R__<description>.sql`).
+    Repeatable,
+}
+
+/// The information found in the name of a Flyway migration file, see [`parse_flyway_file_name`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlywayFileName {
+    /// The kind of the migration.
+    pub kind: FlywayKind,
+
+    /// The version of the migration with its parts separated by dots (`None` for repeatable migrations).
+    pub version: Option<String>,
+
+    /// The description of the migration, with its underscores replaced by spaces.
+    pub description: String,
+}
+
+// A directive found in a comment of a migration file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Directive {
+    Up,
+    Down,
+    StatementBegin,
+    StatementEnd,
+    NoTransaction,
+}
+
+/// Splits a migration file into its up and down statements.
+///
+/// The directives of dbmate, goose and sql-migrate are recognized in the `--` comments preceding or following the
+/// statements (see [`MigrationFormat`]), they are removed from the comments of the statements. The statements preceding
+/// the first `Up` or `Down` directive are up statements, so a file without directives (ex: a Flyway migration) only
+/// contains up statements.
+///
+/// The statements between a `StatementBegin` and a `StatementEnd` directive are returned as a single statement (ex: the
+/// body of a function using semicolons), its tokens include the delimiters of the statements it is made of.
+///
+/// # Examples
+///
+/// ```rust
+/// use loose_sqlparser::{parse_migration, MigrationFormat};
+/// let migration = parse_migration(
+///     "-- +goose Up\nCREATE TABLE t (id INT);\n\
+///      -- +goose StatementBegin\nCREATE PROCEDURE p() BEGIN SELECT 1; SELECT 2; END;\n-- +goose StatementEnd\n\
+///      -- +goose Down\nDROP PROCEDURE p;\nDROP TABLE t;\n",
+/// );
+/// assert_eq!(migration.format, Some(MigrationFormat::Goose));
+/// assert_eq!(migration.up.len(), 2);
+/// assert_eq!(migration.up[1].sql(), "CREATE PROCEDURE p() BEGIN SELECT 1; SELECT 2; END;");
+/// assert_eq!(migration.down.iter().map(|s| s.sql()).collect::<Vec<_>>(), ["DROP PROCEDURE p;", "DROP TABLE t;"]);
+/// ```
+pub fn parse_migration(sql: &str) -> Migration<'_> {
+    parse_migration_with_options(sql, Options::default())
+}
+
+/// Splits a migration file using the given options, see [`parse_migration`].
+pub fn parse_migration_with_options(sql: &str, options: Options) -> Migration<'_> {
+    let mut migration = Migration { format: None, up: Vec::new(), down: Vec::new(), transaction: true };
+    let mut is_down = false;
+    // The statements between `StatementBegin` and `StatementEnd`.
+    let mut block: Option<Vec<Statement>> = None;
+
+    for mut statement in loose_sqlparse_with_options(sql, options) {
+        // The directives preceding the statement apply to the statement, those following it apply to the next
+        // statements. Both are removed from the tokens of the statement.
+        let mut parse = |token: &Token| {
+            let (format, directive, no_transaction) = parse_directive(token)?;
+            migration.format.get_or_insert(format);
+            migration.transaction &= !no_transaction;
+            Some(directive)
+        };
+        let mut leading = Vec::new();
+        statement.leading.retain(|token| match parse(token) {
+            Some(directive) => {
+                leading.push(directive);
+                false
+            }
+            None => true,
+        });
+        // A statement made of comments only has no leading comments, its directives are in its tokens.
+        let is_code = |token: &Token| !token.is_comment() && !token.is_statement_delimiter();
+        let first = statement.tokens.iter().position(is_code).unwrap_or(statement.tokens.len());
+        let last = statement.tokens.iter().rposition(is_code).unwrap_or(0);
+        let mut trailing = Vec::new();
+        let mut index = 0;
+        statement.tokens.retain(|token| {
+            index += 1;
+            let is_inner = first < index && index <= last + 1;
+            let Some(directive) = (!is_inner).then(|| parse(token)).flatten() else {
+                return true;
+            };
+            if index <= first {
+                leading.push(directive);
+            } else {
+                trailing.push(directive);
+            }
+            false
+        });
+
+        for directive in leading {
+            apply_directive(directive, &mut migration, &mut is_down, &mut block);
+        }
+        if !statement.is_empty() {
+            match block.as_mut() {
+                Some(statements) => statements.push(statement),
+                None if is_down => migration.down.push(statement),
+                None => migration.up.push(statement),
+            }
+        }
+        for directive in trailing {
+            apply_directive(directive, &mut migration, &mut is_down, &mut block);
+        }
+    }
+    // An unterminated block ends with the file.
+    apply_directive(Directive::StatementEnd, &mut migration, &mut is_down, &mut block);
+    migration
+}
+
+// Updates the state of the parsing of a migration file with a directive.
+fn apply_directive<'s>(
+    directive: Directive,
+    migration: &mut Migration<'s>,
+    is_down: &mut bool,
+    block: &mut Option<Vec<Statement<'s>>>,
+) {
+    match directive {
+        Directive::Up | Directive::Down => {
+            apply_directive(Directive::StatementEnd, migration, is_down, block);
+            *is_down = directive == Directive::Down;
+        }
+        Directive::StatementBegin => {
+            apply_directive(Directive::StatementEnd, migration, is_down, block);
+            *block = Some(Vec::new());
+        }
+        Directive::StatementEnd => {
+            let Some(statements) = block.take() else {
+                return;
+            };
+            let mut statements = statements.into_iter();
+            let Some(mut statement) = statements.next() else {
+                return;
+            };
+            // The leading comments of the following statements are inner comments of the block.
+            for mut next in statements {
+                statement.tokens.append(&mut next.leading);
+                statement.tokens.append(&mut next.tokens);
+                statement.diagnostics.append(&mut next.diagnostics);
+            }
+            if *is_down {
+                migration.down.push(statement);
+            } else {
+                migration.up.push(statement);
+            }
+        }
+        Directive::NoTransaction => {}
+    }
+}
+
+// Parses a line comment of a migration file, returns its directive and whether it disables the transaction of the
+// migration (ex: `-- migrate:up transaction:false`).
+fn parse_directive(token: &Token) -> Option<(MigrationFormat, Directive, bool)> {
+    let TokenValue::Comment(comment) = token.value else {
+        return None;
+    };
+    let text = comment.strip_prefix("--")?.trim();
+    let (format, rest) = if let Some(rest) = text.strip_prefix("migrate:") {
+        (MigrationFormat::Dbmate, rest)
+    } else if let Some(rest) = text.strip_prefix("+goose ") {
+        (MigrationFormat::Goose, rest)
+    } else if let Some(rest) = text.strip_prefix("+migrate ") {
+        (MigrationFormat::SqlMigrate, rest)
+    } else {
+        return None;
+    };
+    let words: Vec<_> = rest.split_whitespace().map(|word| word.to_ascii_lowercase()).collect();
+    let words: Vec<_> = words.iter().map(String::as_str).collect();
+    let is_dbmate = format == MigrationFormat::Dbmate;
+    let (directive, options) = match words.as_slice() {
+        ["up", options @ ..] => (Directive::Up, options),
+        ["down", options @ ..] => (Directive::Down, options),
+        ["statementbegin"] if !is_dbmate => (Directive::StatementBegin, &[][..]),
+        ["statementend"] if !is_dbmate => (Directive::StatementEnd, &[][..]),
+        ["no", "transaction"] if format == MigrationFormat::Goose => (Directive::NoTransaction, &[][..]),
+        _ => return None,
+    };
+    let no_transaction = directive == Directive::NoTransaction
+        || options.iter().any(|option| matches!(*option, "transaction:false" | "notransaction"));
+    Some((format, directive, no_transaction))
+}
+
+/// Parses the name of a Flyway migration file (ex: `V1_2__Add_users.sql`).
+///
+/// The name can include the path of the file. Returns `None` if the name doesn't follow the Flyway naming convention:
+/// a `V`, `U` or `R` prefix, a version made of numbers separated by dots or underscores (no version for repeatable
+/// migrations), a `__` separator, a description and the `.sql` suffix.
+///
+/// # Examples
+///
+/// ```rust
+/// use loose_sqlparser::{parse_flyway_file_name, FlywayKind};
+/// let name = parse_flyway_file_name("db/migration/V1_2__Add_users.sql").unwrap();
+/// assert_eq!(name.kind, FlywayKind::Versioned);
+/// assert_eq!(name.version.as_deref(), Some("1.2"));
+/// assert_eq!(name.description, "Add users");
+/// assert_eq!(parse_flyway_file_name("R__views.sql").unwrap().version, None);
+/// assert_eq!(parse_flyway_file_name("V1__missing_suffix"), None);
+/// ```
+pub fn parse_flyway_file_name(name: &str) -> Option<FlywayFileName> {
+    let name = name.rsplit(['/', '\\']).next()?;
+    let stem =
+        name.len().checked_sub(4).filter(|&i| name.is_char_boundary(i) && name[i..].eq_ignore_ascii_case(".sql"))?;
+    let (prefix, description) = name[..stem].split_once("__")?;
+    let kind = match prefix.get(..1)? {
+        "V" => FlywayKind::Versioned,
+        "U" => FlywayKind::Undo,
+        "R" => FlywayKind::Repeatable,
+        _ => return None,
+    };
+    let version = &prefix[1..];
+    let version = match kind {
+        FlywayKind::Repeatable if version.is_empty() => None,
+        FlywayKind::Repeatable => return None,
+        _ => {
+            let parts: Vec<_> = version.split(['.', '_']).collect();
+            if !parts.iter().all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit())) {
+                return None;
+            }
+            Some(parts.join("."))
+        }
+    };
+    Some(FlywayFileName { kind, version, description: description.replace('_', " ") })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parse_flyway_file_name, parse_migration, FlywayFileName, FlywayKind, MigrationFormat};
+
+    fn sql(statements: &[crate::Statement]) -> Vec<String> {
+        statements.iter().map(|statement| statement.sql().to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_migration_dbmate() {
+        let migration = parse_migration(
+            "-- migrate:up transaction:false\nCREATE INDEX CONCURRENTLY i ON t (a);\n\n\
+             -- migrate:down\nDROP INDEX i;\n",
+        );
+        assert_eq!(migration.format, Some(MigrationFormat::Dbmate));
+        assert!(!migration.transaction);
+        assert_eq!(sql(&migration.up), ["CREATE INDEX CONCURRENTLY i ON t (a);"]);
+        assert_eq!(sql(&migration.down), ["DROP INDEX i;"]);
+    }
+
+    #[test]
+    fn test_parse_migration_goose() {
+        let migration = parse_migration(
+            "-- +goose NO TRANSACTION\n-- +goose Up\n-- +goose StatementBegin\nCREATE FUNCTION f() BEGIN\n  \
+             SELECT 1;\nEND;\n-- +goose StatementEnd\nSELECT 2;\n-- +goose Down\n-- +goose StatementBegin\n\
+             DROP FUNCTION f; DROP TABLE t\n-- +goose StatementEnd\n",
+        );
+        assert_eq!(migration.format, Some(MigrationFormat::Goose));
+        assert!(!migration.transaction);
+        assert_eq!(sql(&migration.up), ["CREATE FUNCTION f() BEGIN\n  SELECT 1;\nEND;", "SELECT 2;"]);
+        assert_eq!(sql(&migration.down), ["DROP FUNCTION f; DROP TABLE t"]);
+    }
+
+    #[test]
+    fn test_parse_migration_sql_migrate() {
+        let migration = parse_migration("SELECT 0;\n-- +migrate Up\nSELECT 1;\n-- +migrate Down\nSELECT 2;");
+        assert_eq!(migration.format, Some(MigrationFormat::SqlMigrate));
+        assert!(migration.transaction);
+        assert_eq!(sql(&migration.up), ["SELECT 0;", "SELECT 1;"]);
+        assert_eq!(sql(&migration.down), ["SELECT 2;"]);
+
+        let migration = parse_migration("CREATE TABLE t (a INT);\n/* +goose Down */\n");
+        assert_eq!(migration.format, None);
+        assert_eq!(sql(&migration.up), ["CREATE TABLE t (a INT);"]);
+        assert!(migration.down.is_empty());
+    }
+
+    #[test]
+    fn test_parse_flyway_file_name() {
+        assert_eq!(
+            parse_flyway_file_name("U2.1_3__Drop_users.SQL"),
+            Some(FlywayFileName {
+                kind: FlywayKind::Undo,
+                version: Some("2.1.3".to_string()),
+                description: "Drop users".to_string()
+            })
+        );
+        assert_eq!(parse_flyway_file_name("V__no_version.sql"), None);
+        assert_eq!(parse_flyway_file_name("R1__versioned_repeatable.sql"), None);
+        assert_eq!(parse_flyway_file_name("X1__unknown.sql"), None);
+        assert_eq!(parse_flyway_file_name("V1_init.sql"), None);
+    }
+}
@@ -0,0 +1,192 @@
+use crate::tokens::leaves;
+use crate::{loose_sqlparse_with_options, Options, Statement, Token, TokenValue};
+
+/// Options of [`Statement::minify`], [`minify`] and [`minify_with_options`].
+#[derive(Debug, Clone)]
+pub struct MinifyOptions {
+    /// Whether optimizer hints (`/*+ ... */`) are kept.
+    /// The default is `true`.
+    pub keep_hints: bool,
+
+    /// Whether MySQL executable comments (`/*! ... */`) are kept.
+    /// The default is `true`, as their content is executed by MySQL.
+    pub keep_executable_comments: bool,
+}
+
+impl Default for MinifyOptions {
+    fn default() -> Self {
+        Self { keep_hints: true, keep_executable_comments: true }
+    }
+}
+
+/// Minifies all the statements of the given SQL, see [`Statement::minify`].
+///
+/// # Examples
+///
+/// ```rust
+/// use loose_sqlparser::{minify, MinifyOptions};
+/// assert_eq!(minify("SELECT 1; -- one\nSELECT 2", &MinifyOptions::default()), "SELECT 1;SELECT 2");
+/// ```
+pub fn minify(sql: &str, options: &MinifyOptions) -> String {
+    minify_with_options(sql, options, Options::default())
+}
+
+/// Minifies all the statements of the given SQL parsed with the given options, see [`minify`].
+///
+/// With [`Options::psql`], a psql meta-command is followed by a new line since it runs until the end of the line.
+///
+/// # Examples
+///
+/// ```rust
+/// use loose_sqlparser::{minify_with_options, MinifyOptions, Options};
+/// let options = Options { psql: true, ..Default::default() };
+/// assert_eq!(
+///     minify_with_options("\\set id 1\nSELECT * FROM t WHERE id = :id;", &MinifyOptions::default(), options),
+///     "\\set id 1\nSELECT*FROM t WHERE id= :id;"
+/// );
+/// ```
+pub fn minify_with_options(sql: &str, minify_options: &MinifyOptions, options: Options) -> String {
+    let mut minified = String::new();
+    let mut is_meta_command = false;
+    for stmt in loose_sqlparse_with_options(sql, options) {
+        if is_meta_command && !minified.ends_with('\n') {
+            minified.push('\n');
+        }
+        minified.push_str(&stmt.minify(minify_options));
+        is_meta_command = stmt.is_meta_command();
+    }
+    minified
+}
+
+impl Statement<'_> {
+    /// Returns the SQL of the statement with its comments removed and its whitespace reduced to the minimum.
+    ///
+    /// A single space is kept between two tokens only when it's needed to separate them (ex: between two keywords or
+    /// between two operators). The content of the tokens, like string constants or dollar-quoted bodies, is never
+    /// changed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use loose_sqlparser::{loose_sqlparse, MinifyOptions};
+    /// let sql = "SELECT /*+ INDEX(t) */ a, 'x  y'\n  FROM t -- comment\n WHERE a = - 1";
+    /// let stmt = loose_sqlparse(sql).next().unwrap();
+    /// assert_eq!(stmt.minify(&MinifyOptions::default()), "SELECT /*+ INDEX(t) */ a,'x  y' FROM t WHERE a= -1");
+    /// let options = MinifyOptions { keep_hints: false, ..Default::default() };
+    /// assert_eq!(stmt.minify(&options), "SELECT a,'x  y' FROM t WHERE a= -1");
+    /// ```
+    pub fn minify(&self, options: &MinifyOptions) -> String {
+        let mut sql = String::with_capacity(self.sql().len());
+        let mut previous: Option<&Token> = None;
+        // Whether the tokens are inside a tokenized executable comment being removed.
+        let mut in_executable_comment = false;
+        for token in leaves(&self.tokens) {
+            match token.value {
+                TokenValue::Comment(_) => continue,
+                TokenValue::OptimizerHint(_) if !options.keep_hints => continue,
+                TokenValue::ExecutableComment(value) if !options.keep_executable_comments => {
+                    // A tokenized executable comment starts with `/*!NNNNN` and ends with a separate `*/` token.
+                    in_executable_comment = !value.ends_with("*/") && !in_executable_comment;
+                    continue;
+                }
+                _ if in_executable_comment => continue,
+                _ => {}
+            }
+            if let TokenValue::CopyData(data) = token.value {
+                // The data of `COPY ... FROM STDIN` is kept as is on its own lines.
+                sql.push('\n');
+                sql.push_str(data);
+                sql.push('\n');
+                previous = None;
+                continue;
+            }
+            if previous.is_some_and(|previous| needs_space(previous, token)) {
+                sql.push(' ');
+            }
+            sql.push_str(token.value.as_ref());
+            previous = Some(token);
+        }
+        sql
+    }
+}
+
+// Returns whether a space is needed between two consecutive tokens so they are not read as different tokens.
+fn needs_space(previous: &Token, next: &Token) -> bool {
+    use TokenValue::*;
+    match (&previous.value, &next.value) {
+        // Hints and executable comments are kept apart from the other tokens to never be merged with an operator.
+        (Comment(_) | OptimizerHint(_) | ExecutableComment(_), _)
+        | (_, Comment(_) | OptimizerHint(_) | ExecutableComment(_)) => true,
+        (_, StatementDelimiter(_)) | (StatementDelimiter(_), _) => false,
+        (Any("(" | ")" | "," | "." | "[" | "]"), _) | (_, Any("(" | ")" | "," | "[" | "]")) => false,
+        // `1 .x` must not become `1.x`.
+        (NumericConstant(_), Any(".")) => true,
+        (_, Any(".")) => false,
+        // Unknown punctuation keeps the original spacing.
+        (Any(_), _) | (_, Any(_)) => previous.end.offset != next.start.offset,
+        // `a - -1` must not become `a--1`, `= ?` must not become `=?`.
+        (Operator(_), Operator(_) | ParameterMarker(_) | Variable(..))
+        | (ParameterMarker(_) | Variable(..), Operator(_)) => true,
+        // `1e - 1` must not become `1e-1`.
+        (NumericConstant(value), Operator(_)) => value.ends_with(['e', 'E']),
+        (Operator(_), _) | (_, Operator(_)) => false,
+        // Identifiers, keywords, constants and parameter markers.
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{loose_sqlparse_with_options, minify, minify_with_options, MinifyOptions, Options};
+
+    #[test]
+    fn test_minify() {
+        let options = MinifyOptions::default();
+        assert_eq!(
+            minify("SELECT a . b , count( * ) ,t.c::int\n  FROM  t\n  WHERE a IN ( 1, 2 ) AND b >= -1.5;", &options),
+            "SELECT a.b,count(*),t.c::int FROM t WHERE a IN(1,2)AND b>= -1.5;"
+        );
+        assert_eq!(
+            minify("SELECT a - -1, 1 .x, 'a' 'b', \"a\" \"b\"", &options),
+            "SELECT a- -1,1 .x,'a' 'b',\"a\" \"b\""
+        );
+        assert_eq!(minify("SELECT * FROM t WHERE a = ? AND b = :b", &options), "SELECT*FROM t WHERE a= ? AND b= :b");
+        assert_eq!(
+            minify("CREATE FUNCTION f() AS $$\n  SELECT  1;\n$$ LANGUAGE sql;\n\nSELECT E'a\\n  b'", &options),
+            "CREATE FUNCTION f()AS $$\n  SELECT  1;\n$$ LANGUAGE sql;SELECT E'a\\n  b'"
+        );
+        assert_eq!(
+            minify("COPY t FROM stdin;\n1\t a\n\\.\nSELECT  1", &options),
+            "COPY t FROM stdin;\n1\t a\n\\.\nSELECT 1"
+        );
+        // Invalid SQL is minified too.
+        assert_eq!(minify("SELECT ((( 'unterminated  string", &options), "SELECT((('unterminated  string");
+    }
+
+    #[test]
+    fn test_minify_comments() {
+        let sql = "/* header */ SELECT /*+ NO_INDEX(t) */ 1 /*!50001 , 2 */ # mysql\n-- end";
+        assert_eq!(minify(sql, &MinifyOptions::default()), "SELECT /*+ NO_INDEX(t) */ 1 /*!50001 , 2 */");
+        let options = MinifyOptions { keep_hints: false, keep_executable_comments: false };
+        assert_eq!(minify(sql, &options), "SELECT 1");
+
+        // Tokenized executable comments.
+        let tokenize = Options { tokenize_executable_comments: true, ..Default::default() };
+        let stmt = loose_sqlparse_with_options("SELECT 1 /*!50001 ,  2 */ ,  3", tokenize).next().unwrap();
+        assert_eq!(stmt.minify(&MinifyOptions::default()), "SELECT 1 /*!50001 ,2 */ ,3");
+        assert_eq!(stmt.minify(&options), "SELECT 1,3");
+    }
+
+    #[test]
+    fn test_minify_psql() {
+        let options = Options { psql: true, ..Default::default() };
+        assert_eq!(
+            minify_with_options(
+                "SELECT  1 \\gexec\n-- connect\n\\connect db\n\\copy t from stdin\n1\n\\.\nSELECT :'x' ;",
+                &MinifyOptions::default(),
+                options
+            ),
+            "SELECT 1\\gexec\n\\connect db\n\\copy t from stdin\n1\n\\.\nSELECT :'x';"
+        );
+    }
+}
@@ -0,0 +1,162 @@
+use crate::{loose_sqlparse_with_options, CommentPlacement, Error, Options, Position, Statement};
+
+/// A query of a named query file, see [`named_queries`].
+#[derive(Debug)]
+pub struct NamedQuery<'s> {
+    /// The name of the query (ex: `FindUser` for `-- name: FindUser :one`).
+    pub name: &'s str,
+
+    /// The annotation flags following the name, without their leading colon (ex: `["one"]` for
+    /// `-- name: FindUser :one`).
+    pub flags: Vec<&'s str>,
+
+    /// The text of the comments between the name and the statement.
+    pub docs: Vec<&'s str>,
+
+    /// The SQL of the statement without its leading comments.
+    pub sql: &'s str,
+
+    /// The statement of the query, its leading comments include the name and the documentation.
+    pub statement: Statement<'s>,
+}
+
+/// The result of [`named_queries`].
+#[derive(Debug)]
+pub struct NamedQueries<'s> {
+    /// The named queries in the order they appear.
+    pub queries: Vec<NamedQuery<'s>>,
+
+    /// The problems found in the file: [`Error::DuplicateQueryName`], [`Error::MissingQueryName`] and
+    /// [`Error::EmptyQuery`].
+    pub errors: Vec<Error>,
+}
+
+impl<'s> NamedQueries<'s> {
+    /// Returns the query with the given name.
+    pub fn get(&self, name: &str) -> Option<&NamedQuery<'s>> {
+        self.queries.iter().find(|query| query.name == name)
+    }
+}
+
+/// Splits a file of named queries (as used by sqlc or yesql) into queries.
+///
+/// Each query is a statement preceded by a `-- name: <name> [:flag ...]` comment. The comments between the name and
+/// the statement are the documentation of the query, the comments preceding the name (ex: a header of the file) are
+/// ignored. Empty statements are ignored unless they have a name (reported with [`Error::EmptyQuery`]), non-empty
+/// statements without a name are reported with [`Error::MissingQueryName`] and not returned, and queries reusing the
+/// name of a previous query are reported with [`Error::DuplicateQueryName`] (only the first one can be found with
+/// [`NamedQueries::get`]).
+///
+/// # Examples
+///
+/// ```rust
+/// use loose_sqlparser::named_queries;
+/// let file = named_queries(
+///     "-- name: FindUser :one\n-- Returns a user by id.\nSELECT * FROM users WHERE id = $1;\n\n\
+///      -- name: DeleteUser :exec\nDELETE FROM users WHERE id = $1;",
+/// );
+/// assert!(file.errors.is_empty());
+/// let query = file.get("FindUser").unwrap();
+/// assert_eq!(query.flags, ["one"]);
+/// assert_eq!(query.docs, ["Returns a user by id."]);
+/// assert_eq!(query.sql, "SELECT * FROM users WHERE id = $1;");
+/// assert_eq!(file.queries[1].name, "DeleteUser");
+/// ```
+pub fn named_queries(sql: &str) -> NamedQueries<'_> {
+    named_queries_with_options(sql, Options::default())
+}
+
+/// Splits a file of named queries using the given options, see [`named_queries`].
+pub fn named_queries_with_options(sql: &str, options: Options) -> NamedQueries<'_> {
+    let mut queries: Vec<NamedQuery> = Vec::new();
+    let mut errors = Vec::new();
+    for statement in loose_sqlparse_with_options(sql, options) {
+        let mut header = None;
+        let mut docs = Vec::new();
+        // The comments of an empty statement are all leading comments.
+        for comment in statement.comments().into_iter().filter(|c| c.placement == CommentPlacement::Leading) {
+            if let Some(annotation) = parse_name(comment.text) {
+                // The last name wins, the comments preceding it are not part of the documentation.
+                header = Some(annotation);
+                docs.clear();
+            } else if header.is_some() {
+                docs.push(comment.text);
+            }
+        }
+        if statement.is_empty() {
+            if let Some((name, _)) = header {
+                errors.push(Error::EmptyQuery { name: name.to_string(), position: query_start(&statement).clone() });
+            }
+            continue;
+        }
+        let Some((name, flags)) = header else {
+            errors.push(Error::MissingQueryName { position: query_start(&statement).clone() });
+            continue;
+        };
+        if let Some(first) = queries.iter().find(|query| query.name == name) {
+            errors.push(Error::DuplicateQueryName {
+                name: name.to_string(),
+                first: query_start(&first.statement).clone(),
+                second: query_start(&statement).clone(),
+            });
+        }
+        let sql = &statement.input[statement.start().offset..statement.end().offset];
+        queries.push(NamedQuery { name, flags, docs, sql, statement });
+    }
+    NamedQueries { queries, errors }
+}
+
+// Returns the start position of a query, including its leading comments.
+fn query_start<'a>(statement: &'a Statement) -> &'a Position {
+    statement.leading.first().map_or(statement.start(), |comment| &comment.start)
+}
+
+// Parses the text of a `name: <name> [:flag ...]` comment.
+fn parse_name(text: &str) -> Option<(&str, Vec<&str>)> {
+    let prefix = text.get(..5).filter(|prefix| prefix.eq_ignore_ascii_case("name:"))?;
+    let mut words = text[prefix.len()..].split_whitespace();
+    let name = words.next()?;
+    Some((name, words.map(|flag| flag.strip_prefix(':').unwrap_or(flag)).collect()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{named_queries, Error};
+
+    #[test]
+    fn test_named_queries() {
+        let file = named_queries(
+            "-- Queries of the users.\n\n\
+             -- name: list-users\n-- Lists the users\n/* ordered by name */\nSELECT * FROM users ORDER BY name;\n\
+             -- NAME: save-user! :exec :audit\nINSERT INTO users (name) VALUES (:name)\n;\n\
+             -- trailing comment",
+        );
+        assert!(file.errors.is_empty());
+        assert_eq!(file.queries.len(), 2);
+        assert_eq!(file.queries[0].name, "list-users");
+        assert!(file.queries[0].flags.is_empty());
+        assert_eq!(file.queries[0].docs, ["Lists the users", "ordered by name"]);
+        assert_eq!(file.queries[0].sql, "SELECT * FROM users ORDER BY name;");
+        assert_eq!(file.queries[0].statement.leading_comments()[0].token.start.line, 1);
+        assert_eq!(file.queries[1].name, "save-user!");
+        assert_eq!(file.queries[1].flags, ["exec", "audit"]);
+    }
+
+    #[test]
+    fn test_named_queries_errors() {
+        let file = named_queries(
+            "-- name: A\nSELECT 1;\nSELECT 2;\n-- name:\nSELECT 3;\n-- name: A :many\nSELECT 4;\n-- name: B\n;",
+        );
+        assert_eq!(file.queries.len(), 2);
+        assert_eq!(file.get("A").unwrap().sql, "SELECT 1;");
+        assert_eq!(file.errors.len(), 4);
+        assert!(matches!(&file.errors[0], Error::MissingQueryName { position } if position.line == 3));
+        assert!(matches!(&file.errors[1], Error::MissingQueryName { position } if position.line == 4));
+        assert!(matches!(
+            &file.errors[2],
+            Error::DuplicateQueryName { name, first, second } if name == "A" && first.line == 1 && second.line == 6
+        ));
+        assert!(matches!(&file.errors[3], Error::EmptyQuery { name, position } if name == "B" && position.line == 8));
+        assert_eq!(file.errors[3].to_string(), "query 'B' at 8:1 has no statement");
+    }
+}
@@ -1,17 +1,112 @@
 #[cfg(feature = "serialize")]
 use serde::Deserialize;
 
-#[cfg_attr(feature = "serialize", derive(Deserialize))]
+#[cfg_attr(feature = "serialize", derive(Deserialize), serde(default))]
 #[derive(Debug, Clone)]
 /// Parser options.
 pub struct Options {
     /// The delimiter used to separate statements.
     /// The default is `;`.
     pub statement_delimiter: String,
+
+    /// The SQL dialect of the input.
+    /// The default is [`Dialect::Generic`].
+    pub dialect: Dialect,
+
+    /// Whether the content of MySQL executable comments (`/*! ... */`) is tokenized as SQL, see
+    /// [`crate::TokenValue::ExecutableComment`].
+    /// The default is `false`.
+    pub tokenize_executable_comments: bool,
+
+    /// Whether the input is a psql script.
+    /// When enabled, backslash meta-commands (ex: `\connect db`) are captured as [`crate::TokenValue::MetaCommand`]
+    /// statements ending at the end of the line, and `:name`, `:'name'` and `:"name"` are captured as
+    /// [`crate::TokenValue::Variable`]s with the [`crate::VariableScope::Client`] scope instead of parameter markers.
+    /// The default is `false`.
+    pub psql: bool,
+
+    /// Whether backslashes are ordinary characters in the string constants of the [`Dialect::MySql`] dialect (MySQL
+    /// `NO_BACKSLASH_ESCAPES` SQL mode).
+    /// By default, a backslash escapes the following character in MySQL string constants (ex: `'It\'s'`). Escaped
+    /// string constants of PostgreSQL (ex: `E'It\'s'`) always honour backslash escapes.
+    /// The default is `false`.
+    pub no_backslash_escapes: bool,
+
+    /// Additional operators recognized by the tokenizer (ex: `"<~>"`), on top of the operators of the dialect.
+    /// The longest operator matching the input is always captured, whether it's a custom operator or not. Operators
+    /// starting with a letter, a digit, an underscore or a whitespace are ignored: the tokenizer reads an identifier, a
+    /// keyword or a number before looking for an operator, so they would never be matched.
+    /// The default is empty.
+    pub custom_operators: Vec<String>,
+
+    /// Whether a question mark is a parameter marker or an operator (ex: the `?` and `?|` JSONB operators of
+    /// PostgreSQL), see [`QuestionMarkMode`].
+    /// The default is `None`: [`QuestionMarkMode::Operator`] for [`Dialect::PostgreSql`] and
+    /// [`QuestionMarkMode::Parameter`] for the other dialects.
+    pub question_mark: Option<QuestionMarkMode>,
+
+    /// The maximum nesting depth of parentheses, brackets, braces and executable comments captured as
+    /// [`crate::TokenValue::Fragment`]s.
+    /// The blocks nested deeper are tokenized flat (their opening and closing characters are ordinary tokens) and
+    /// reported with [`crate::Error::MaxDepthExceeded`] in [`crate::Statement::diagnostics`]. This bounds the recursion
+    /// of the tokenizer and of the functions walking the fragments on untrusted input.
+    /// The default is `128`.
+    pub max_depth: usize,
 }
 
 impl Default for Options {
     fn default() -> Self {
-        Self { statement_delimiter: ";".to_string() }
+        Self {
+            statement_delimiter: ";".to_string(),
+            dialect: Dialect::Generic,
+            tokenize_executable_comments: false,
+            psql: false,
+            no_backslash_escapes: false,
+            custom_operators: Vec::new(),
+            question_mark: None,
+            max_depth: 128,
+        }
     }
 }
+
+/// A SQL dialect.
+///
+/// The parser is not validating so most of the syntax is shared by all the dialects, the dialect is only used when a
+/// construct has a different meaning from one database to another.
+#[cfg_attr(feature = "serialize", derive(Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Dialect {
+    /// A dialect accepting the most common constructs of the other dialects.
+    ///
+    /// Only the operators shared by all the dialects are recognized, the operators of a specific dialect (ex: `->` or
+    /// `:=`) are split into several operators.
+    #[default]
+    Generic,
+    /// PostgreSQL
+    PostgreSql,
+    /// MySQL or MariaDB
+    MySql,
+    /// SQLite
+    Sqlite,
+    /// DuckDB
+    DuckDb,
+    /// Oracle
+    Oracle,
+    /// Microsoft SQL Server
+    SqlServer,
+    /// Snowflake
+    Snowflake,
+}
+
+/// The meaning of a question mark, see [`Options::question_mark`].
+#[cfg_attr(feature = "serialize", derive(Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuestionMarkMode {
+    /// `?` is a parameter marker (ex: SQLite, MySQL).
+    Parameter,
+    /// `?` starts an operator (ex: `?`, `?|` and `?&` in PostgreSQL).
+    Operator,
+    /// `?` is a parameter marker and `??` is an escaped question mark starting an operator (ex: `??|` for `?|`), as
+    /// done by JDBC drivers.
+    Jdbc,
+}
@@ -0,0 +1,361 @@
+use crate::tokens::leaves;
+use crate::{Error, Position, Statement, TokenValue};
+
+/// The style of a parameter marker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParameterStyle {
+    /// Question mark (`?`): SQLite, MySQL, ODBC/JDBC drivers, ...
+    Positional,
+
+    /// Numbered parameter (`$1`: PostgreSQL, `?1`: SQLite, `:1`: Oracle).
+    Numbered,
+
+    /// Named parameter prefixed by a colon (`:name`).
+    NamedColon,
+
+    /// Named parameter prefixed by an at sign (`@name`).
+    NamedAt,
+
+    /// Named parameter prefixed by a dollar sign (`$name`).
+    NamedDollar,
+}
+
+/// A parameter marker found in a statement.
+#[derive(Debug, Clone)]
+pub struct Parameter<'s> {
+    /// The style of the parameter marker.
+    pub style: ParameterStyle,
+
+    /// The parameter marker as found in the statement (ex: `?`, `$1`, `:name`).
+    pub marker: &'s str,
+
+    /// The name of a named parameter, without its prefix (ex: `name` for `:name`).
+    pub name: Option<&'s str>,
+
+    /// The 1-based index of the parameter.
+    ///
+    /// For a numbered parameter this is its number (ex: `2` for `$2`), for a positional parameter this is its position
+    /// among the positional parameters of the statement. Named parameters don't have an index.
+    pub index: Option<usize>,
+
+    /// The position of the first character of the parameter marker.
+    pub start: Position,
+
+    /// The position of the last character of the parameter marker.
+    pub end: Position,
+}
+
+/// A statement rewritten with a different parameter style, see [`Statement::rewrite_parameters`].
+#[derive(Debug, Clone)]
+pub struct RewrittenParameters<'s> {
+    /// The SQL of the statement using the new parameter style.
+    pub sql: String,
+
+    /// The parameters of the original statement in the order they must be bound to the rewritten statement.
+    ///
+    /// - For positional parameters (`?`), there is one entry per parameter marker of the rewritten statement.
+    /// - For numbered parameters (`$n`), the entry at index `n - 1` is the parameter to be bound to `$n`.
+    /// - For named parameters, there is one entry per distinct name.
+    pub parameters: Vec<Parameter<'s>>,
+}
+
+impl<'s> Statement<'s> {
+    /// Returns the parameter markers of the statement in the order they appear (including those nested in
+    /// parentheses).
+    ///
+    /// Returns an error if the statement mixes parameter markers of different styles, or numbered parameter markers
+    /// with different prefixes (ex: `:1` and `$1`).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use loose_sqlparser::{loose_sqlparse, ParameterStyle};
+    /// let stmt = loose_sqlparse("SELECT * FROM users WHERE id = :id AND name IN (:name, 'x')").next().unwrap();
+    /// let parameters = stmt.parameters().unwrap();
+    /// assert_eq!(parameters.len(), 2);
+    /// assert_eq!(parameters[0].style, ParameterStyle::NamedColon);
+    /// assert_eq!(parameters[0].name, Some("id"));
+    /// assert_eq!(parameters[1].name, Some("name"));
+    ///
+    /// let stmt = loose_sqlparse("SELECT * FROM users WHERE id = ? AND name = :name").next().unwrap();
+    /// assert!(stmt.parameters().is_err());
+    /// ```
+    pub fn parameters(&self) -> Result<Vec<Parameter<'s>>, Error> {
+        let mut parameters: Vec<Parameter<'s>> = Vec::new();
+        for token in leaves(&self.tokens) {
+            let TokenValue::ParameterMarker(marker) = token.value else {
+                continue;
+            };
+            let (prefix, suffix) = marker.split_at(1);
+            let (style, name, index) = if suffix.is_empty() {
+                match prefix {
+                    "?" => {
+                        let position = parameters.iter().filter(|p| p.style == ParameterStyle::Positional).count();
+                        (ParameterStyle::Positional, None, Some(position + 1))
+                    }
+                    // A `:`, `@` or `$` without a name is not a parameter marker (ex: `x:=1`).
+                    _ => continue,
+                }
+            } else if let Ok(number) = suffix.parse::<usize>() {
+                (ParameterStyle::Numbered, None, Some(number))
+            } else {
+                match prefix {
+                    ":" => (ParameterStyle::NamedColon, Some(suffix), None),
+                    "@" => (ParameterStyle::NamedAt, Some(suffix), None),
+                    "$" => (ParameterStyle::NamedDollar, Some(suffix), None),
+                    _ => continue,
+                }
+            };
+            if let Some(first) = parameters.first() {
+                // Numbered parameters must also use the same prefix (ex: `:1` and `$1` are not the same parameter).
+                if first.style != style || first.marker[..1] != *prefix {
+                    return Err(Error::MixedParameterStyles {
+                        expected: first.style,
+                        found: style,
+                        position: token.start.clone(),
+                    });
+                }
+            }
+            parameters.push(Parameter {
+                style,
+                marker,
+                name,
+                index,
+                start: token.start.clone(),
+                end: token.end.clone(),
+            });
+        }
+        Ok(parameters)
+    }
+
+    /// Rewrites the parameter markers of the statement using the given style.
+    ///
+    /// - [`ParameterStyle::Positional`]: each parameter marker is replaced by `?`, a named parameter used several times
+    ///   must be bound several times.
+    /// - [`ParameterStyle::Numbered`]: parameter markers are replaced by `$1`, `$2`, ... in order of appearance, a
+    ///   named parameter used several times is always replaced by the same number.
+    /// - [`ParameterStyle::NamedColon`], [`ParameterStyle::NamedAt`], [`ParameterStyle::NamedDollar`]: the prefix of
+    ///   named parameters is replaced. Only named parameters can be rewritten using a named style.
+    ///
+    /// Everything else in the statement (including string literals, comments, `::` casts and variables such as
+    /// `@@version`) is left untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use loose_sqlparser::{loose_sqlparse, ParameterStyle};
+    /// let stmt = loose_sqlparse("SELECT * FROM t WHERE a = :a AND b = :b OR a = :a::int").next().unwrap();
+    ///
+    /// let rewritten = stmt.rewrite_parameters(ParameterStyle::Numbered).unwrap();
+    /// assert_eq!(rewritten.sql, "SELECT * FROM t WHERE a = $1 AND b = $2 OR a = $1::int");
+    /// assert_eq!(rewritten.parameters.iter().map(|p| p.name.unwrap()).collect::<Vec<_>>(), ["a", "b"]);
+    ///
+    /// let rewritten = stmt.rewrite_parameters(ParameterStyle::Positional).unwrap();
+    /// assert_eq!(rewritten.sql, "SELECT * FROM t WHERE a = ? AND b = ? OR a = ?::int");
+    /// assert_eq!(rewritten.parameters.iter().map(|p| p.name.unwrap()).collect::<Vec<_>>(), ["a", "b", "a"]);
+    /// ```
+    pub fn rewrite_parameters(&self, style: ParameterStyle) -> Result<RewrittenParameters<'s>, Error> {
+        let parameters = self.parameters()?;
+        let mut edits = self.edits();
+        let mut bindings: Vec<Parameter<'s>> = Vec::new();
+        for parameter in parameters {
+            let range = parameter.start.offset..parameter.end.offset;
+            let marker = match style {
+                ParameterStyle::Positional => {
+                    bindings.push(parameter);
+                    "?".to_string()
+                }
+                ParameterStyle::Numbered => {
+                    // The same parameter (same name or same number) is always bound to the same number.
+                    let same_parameter = |p: &Parameter| match parameter.style {
+                        ParameterStyle::Positional => false,
+                        ParameterStyle::Numbered => p.index == parameter.index,
+                        _ => p.name == parameter.name,
+                    };
+                    let number = match bindings.iter().position(same_parameter) {
+                        Some(position) => position + 1,
+                        None => {
+                            bindings.push(parameter);
+                            bindings.len()
+                        }
+                    };
+                    format!("${}", number)
+                }
+                ParameterStyle::NamedColon | ParameterStyle::NamedAt | ParameterStyle::NamedDollar => {
+                    let Some(name) = parameter.name else {
+                        return Err(Error::UnsupportedParameterConversion { from: parameter.style, to: style });
+                    };
+                    let prefix = match style {
+                        ParameterStyle::NamedColon => ':',
+                        ParameterStyle::NamedAt => '@',
+                        _ => '$',
+                    };
+                    if !bindings.iter().any(|p| p.name == parameter.name) {
+                        bindings.push(parameter);
+                    }
+                    format!("{}{}", prefix, name)
+                }
+            };
+            edits.replace(range, marker)?;
+        }
+        Ok(RewrittenParameters { sql: edits.apply().sql, parameters: bindings })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        loose_sqlparse, loose_sqlparse_with_options, Dialect, Error, Options, ParameterStyle, QuestionMarkMode,
+    };
+
+    // Rewrites the parameters of the given SQL and returns the SQL and the names or indexes of the parameters to bind.
+    fn rewrite(sql: &str, style: ParameterStyle) -> (String, Vec<String>) {
+        let rewritten = loose_sqlparse(sql).next().unwrap().rewrite_parameters(style).unwrap();
+        let parameters = rewritten
+            .parameters
+            .iter()
+            .map(|p| p.name.map(|name| name.to_string()).unwrap_or_else(|| p.index.unwrap().to_string()))
+            .collect();
+        (rewritten.sql, parameters)
+    }
+
+    #[test]
+    fn test_parameters() {
+        let stmt = loose_sqlparse("SELECT ? FROM t WHERE a = (SELECT ?) AND b = '?' /* ? */;").next().unwrap();
+        let parameters = stmt.parameters().unwrap();
+        assert_eq!(parameters.len(), 2);
+        assert!(parameters.iter().all(|p| p.style == ParameterStyle::Positional && p.name.is_none()));
+        assert_eq!(parameters.iter().map(|p| p.index).collect::<Vec<_>>(), [Some(1), Some(2)]);
+        assert_eq!(parameters.iter().map(|p| p.start.column).collect::<Vec<_>>(), [8, 35]);
+        assert_eq!(parameters.iter().map(|p| p.end.column).collect::<Vec<_>>(), [8, 35]);
+
+        let stmt = loose_sqlparse("SELECT $2, $1, $2").next().unwrap();
+        let parameters = stmt.parameters().unwrap();
+        assert!(parameters.iter().all(|p| p.style == ParameterStyle::Numbered));
+        assert_eq!(parameters.iter().map(|p| p.index).collect::<Vec<_>>(), [Some(2), Some(1), Some(2)]);
+        assert_eq!(parameters.iter().map(|p| p.marker).collect::<Vec<_>>(), ["$2", "$1", "$2"]);
+
+        let stmt = loose_sqlparse("SELECT ?1, ?2").next().unwrap();
+        let parameters = stmt.parameters().unwrap();
+        assert_eq!(parameters.iter().map(|p| p.index).collect::<Vec<_>>(), [Some(1), Some(2)]);
+
+        let stmt = loose_sqlparse("SELECT :a, :b, :a").next().unwrap();
+        let parameters = stmt.parameters().unwrap();
+        assert!(parameters.iter().all(|p| p.style == ParameterStyle::NamedColon && p.index.is_none()));
+        assert_eq!(parameters.iter().map(|p| p.name).collect::<Vec<_>>(), [Some("a"), Some("b"), Some("a")]);
+
+        let stmt = loose_sqlparse("SELECT @a").next().unwrap();
+        assert_eq!(stmt.parameters().unwrap()[0].style, ParameterStyle::NamedAt);
+        let stmt = loose_sqlparse("SELECT $a").next().unwrap();
+        assert_eq!(stmt.parameters().unwrap()[0].style, ParameterStyle::NamedDollar);
+
+        assert!(loose_sqlparse("SELECT 1").next().unwrap().parameters().unwrap().is_empty());
+        assert!(loose_sqlparse("SELECT a::int").next().unwrap().parameters().unwrap().is_empty());
+        // A colon in brackets is a slice operator.
+        assert!(loose_sqlparse("SELECT arr[1:3], arr[lo:hi]").next().unwrap().parameters().unwrap().is_empty());
+        let stmt = loose_sqlparse("SELECT * FROM t WHERE id = ANY(ARRAY[:a, :b])").next().unwrap();
+        let parameters = stmt.parameters().unwrap();
+        assert!(parameters.iter().all(|p| p.style == ParameterStyle::NamedColon));
+        assert_eq!(parameters.iter().map(|p| p.name).collect::<Vec<_>>(), [Some("a"), Some("b")]);
+        assert!(loose_sqlparse("SELECT @@version").next().unwrap().parameters().unwrap().is_empty());
+        let mysql = Options { dialect: Dialect::MySql, ..Default::default() };
+        let stmt = loose_sqlparse_with_options("SET @total = @total + ?", mysql).next().unwrap();
+        assert_eq!(stmt.parameters().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_parameters_question_mark_mode() {
+        let count = |sql: &str, dialect: Dialect, question_mark: Option<QuestionMarkMode>| {
+            let options = Options { dialect, question_mark, ..Default::default() };
+            loose_sqlparse_with_options(sql, options).next().unwrap().parameters().unwrap().len()
+        };
+        let jsonb = "SELECT * FROM t WHERE data ? 'a' AND data ?| array['b'] AND data ?& array['c'] AND id = $1";
+        assert_eq!(count(jsonb, Dialect::PostgreSql, None), 1);
+        assert_eq!(count("SELECT ? FROM t WHERE a ?| b", Dialect::Generic, None), 2);
+        assert_eq!(count("SELECT ? FROM t WHERE a ?| b", Dialect::Generic, Some(QuestionMarkMode::Parameter)), 2);
+        assert_eq!(count("SELECT ? FROM t WHERE a ?| b", Dialect::PostgreSql, Some(QuestionMarkMode::Parameter)), 2);
+        assert_eq!(count("SELECT * FROM t WHERE a ? 'k'", Dialect::Generic, Some(QuestionMarkMode::Operator)), 0);
+
+        // JDBC escapes the operators by doubling the question mark.
+        let jdbc = "SELECT * FROM t WHERE data ?? ? AND data ??| ? AND data ??& ? AND id = ?";
+        assert_eq!(count(jdbc, Dialect::PostgreSql, Some(QuestionMarkMode::Jdbc)), 4);
+        let options = Options { question_mark: Some(QuestionMarkMode::Jdbc), ..Default::default() };
+        let stmt = loose_sqlparse_with_options("a??|?", options).next().unwrap();
+        let tokens = stmt.tokens.iter().map(|t| format!("{:?}", t.value)).collect::<Vec<_>>();
+        assert_eq!(tokens, ["IdentifierOrKeyword(\"a\")", "Operator(\"??|\")", "ParameterMarker(\"?\")"]);
+    }
+
+    #[test]
+    fn test_parameters_mixed_styles() {
+        let stmt = loose_sqlparse("SELECT :a, (SELECT $1)").next().unwrap();
+        let error = stmt.parameters().unwrap_err();
+        assert!(matches!(
+            error,
+            Error::MixedParameterStyles {
+                expected: ParameterStyle::NamedColon,
+                found: ParameterStyle::Numbered,
+                position: crate::Position { line: 1, column: 20, offset: 19 },
+            }
+        ));
+        assert_eq!(
+            error.to_string(),
+            "Numbered parameter marker found at 1:20 in a statement using NamedColon parameter markers"
+        );
+        assert!(loose_sqlparse("SELECT ?, $1").next().unwrap().parameters().is_err());
+        assert!(loose_sqlparse("SELECT :a, @b").next().unwrap().parameters().is_err());
+
+        // Numbered parameters with different prefixes are different parameters.
+        let error = loose_sqlparse("SELECT * FROM t WHERE a = :1 AND b = $1").next().unwrap().parameters().unwrap_err();
+        assert_eq!(error.to_string(), "Numbered parameter marker with a different prefix found at 1:38");
+        assert!(loose_sqlparse("SELECT ?1, :2").next().unwrap().rewrite_parameters(ParameterStyle::Numbered).is_err());
+    }
+
+    #[test]
+    fn test_rewrite_parameters() {
+        let sql = "SELECT :a, ':b', \":c\", /* :d */ x::text, @@session.sql_mode, :e, (SELECT :a) -- :f\n;";
+        assert_eq!(
+            rewrite(sql, ParameterStyle::Numbered),
+            (
+                "SELECT $1, ':b', \":c\", /* :d */ x::text, @@session.sql_mode, $2, (SELECT $1) -- :f\n;".to_string(),
+                vec!["a".to_string(), "e".to_string()]
+            )
+        );
+        assert_eq!(
+            rewrite(sql, ParameterStyle::Positional),
+            (
+                "SELECT ?, ':b', \":c\", /* :d */ x::text, @@session.sql_mode, ?, (SELECT ?) -- :f\n;".to_string(),
+                vec!["a".to_string(), "e".to_string(), "a".to_string()]
+            )
+        );
+        assert_eq!(
+            rewrite(sql, ParameterStyle::NamedAt),
+            (
+                "SELECT @a, ':b', \":c\", /* :d */ x::text, @@session.sql_mode, @e, (SELECT @a) -- :f\n;".to_string(),
+                vec!["a".to_string(), "e".to_string()]
+            )
+        );
+        assert_eq!(
+            rewrite("SELECT ?, ?", ParameterStyle::Numbered),
+            ("SELECT $1, $2".to_string(), vec!["1".to_string(), "2".to_string()])
+        );
+        assert_eq!(
+            rewrite("SELECT $2, $1, $2", ParameterStyle::Positional),
+            ("SELECT ?, ?, ?".to_string(), vec!["2".to_string(), "1".to_string(), "2".to_string()])
+        );
+        assert_eq!(rewrite("SELECT 1", ParameterStyle::Numbered), ("SELECT 1".to_string(), vec![]));
+    }
+
+    #[test]
+    fn test_rewrite_parameters_errors() {
+        let stmt = loose_sqlparse("SELECT ?").next().unwrap();
+        assert!(matches!(
+            stmt.rewrite_parameters(ParameterStyle::NamedColon),
+            Err(Error::UnsupportedParameterConversion {
+                from: ParameterStyle::Positional,
+                to: ParameterStyle::NamedColon
+            })
+        ));
+        let stmt = loose_sqlparse("SELECT ?, :a").next().unwrap();
+        assert!(matches!(stmt.rewrite_parameters(ParameterStyle::Numbered), Err(Error::MixedParameterStyles { .. })));
+    }
+}
@@ -0,0 +1,93 @@
+use crate::keywords::is_keyword;
+use crate::tokens::leaves;
+use crate::{Case, Statement, TokenValue};
+use std::borrow::Cow;
+
+/// Options of [`Statement::recase`].
+#[derive(Debug, Clone)]
+pub struct CaseOptions {
+    /// The case of the keywords.
+    /// The default is [`Case::Upper`].
+    pub keywords: Case,
+
+    /// The case of the unquoted identifiers.
+    /// The default is [`Case::Preserve`].
+    pub identifiers: Case,
+}
+
+impl Default for CaseOptions {
+    fn default() -> Self {
+        Self { keywords: Case::Upper, identifiers: Case::Preserve }
+    }
+}
+
+impl Statement<'_> {
+    /// Returns the SQL of the statement with its keywords and unquoted identifiers converted to the requested case.
+    ///
+    /// Quoted identifiers, constants, parameter markers and comments are never changed, and everything else is kept
+    /// byte for byte, including the whitespace. A keyword used as a qualified name (ex: `t.type`) is considered as an
+    /// identifier.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use loose_sqlparser::{loose_sqlparse, Case, CaseOptions};
+    /// let sql = "select Id, \"Name\"\n  from Users u -- List users\n where u.type = 'Admin'";
+    /// let stmt = loose_sqlparse(sql).next().unwrap();
+    /// let options = CaseOptions { keywords: Case::Upper, identifiers: Case::Lower };
+    /// assert_eq!(
+    ///     stmt.recase(&options),
+    ///     "SELECT id, \"Name\"\n  FROM users u -- List users\n WHERE u.type = 'Admin'"
+    /// );
+    /// ```
+    pub fn recase(&self, options: &CaseOptions) -> String {
+        let mut edits = self.edits();
+        let tokens = leaves(&self.tokens);
+        let is_dot = |index: Option<usize>| {
+            index.and_then(|i| tokens.get(i)).is_some_and(|t| matches!(t.value, TokenValue::Any(".")))
+        };
+        for (index, token) in tokens.iter().enumerate() {
+            let TokenValue::IdentifierOrKeyword(value) = token.value else {
+                continue;
+            };
+            let is_qualified = is_dot(index.checked_sub(1)) || is_dot(Some(index + 1));
+            let case = if is_keyword(value) && !is_qualified { options.keywords } else { options.identifiers };
+            if let Cow::Owned(text) = case.apply(value) {
+                edits.replace_token(token, text).expect("tokens do not overlap");
+            }
+        }
+        edits.apply().sql
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{loose_sqlparse, Case, CaseOptions};
+
+    fn recase(sql: &str, keywords: Case, identifiers: Case) -> String {
+        loose_sqlparse(sql).next().unwrap().recase(&CaseOptions { keywords, identifiers })
+    }
+
+    #[test]
+    fn test_recase() {
+        let sql =
+            "Select Count(*), `Col`, $$Body$$, E'Str', :Param, @@Version\n\tFROM S.Tab /* Comment */ wHeRe x::Int;";
+        assert_eq!(
+            recase(sql, Case::Upper, Case::Lower),
+            "SELECT count(*), `Col`, $$Body$$, E'Str', :Param, @@Version\n\tFROM s.tab /* Comment */ WHERE x::INT;"
+        );
+        assert_eq!(
+            recase(sql, Case::Lower, Case::Upper),
+            "select COUNT(*), `Col`, $$Body$$, E'Str', :Param, @@Version\n\tfrom S.TAB /* Comment */ where X::int;"
+        );
+        assert_eq!(recase(sql, Case::Preserve, Case::Preserve), sql);
+    }
+
+    #[test]
+    fn test_recase_qualified_keywords() {
+        assert_eq!(
+            recase("select user.name, t.type, type.id from t", Case::Upper, Case::Lower),
+            "SELECT user.name, t.type, type.id FROM t"
+        );
+    }
+}
@@ -0,0 +1,163 @@
+use crate::tokens::{is_string_constant, leaves};
+use crate::{loose_sqlparse_with_options, EditSet, Options, Statement, Token, TokenValue};
+
+/// Options of [`Statement::redact`].
+#[derive(Debug, Clone)]
+pub struct RedactOptions {
+    /// The text replacing the redacted literals.
+    /// The default is `?`.
+    pub placeholder: String,
+
+    /// Whether the content of comments should be redacted too.
+    /// The default is `false`.
+    pub redact_comments: bool,
+
+    /// The options the statement was parsed with, used to tokenize the SQL of optimizer hints and executable comments
+    /// (ex: the backslash escapes of MySQL string constants).
+    /// The default is [`Options::default`].
+    pub parse_options: Options,
+}
+
+impl Default for RedactOptions {
+    fn default() -> Self {
+        Self { placeholder: "?".to_string(), redact_comments: false, parse_options: Options::default() }
+    }
+}
+
+impl Statement<'_> {
+    /// Returns the SQL of the statement with its string and numeric literals replaced by a placeholder.
+    ///
+    /// The inline data of a `COPY ... FROM STDIN` statement is replaced by a single placeholder line, and the literals
+    /// of the SQL executed from optimizer hints and MySQL executable comments (ex: `/*!50001 SET x = 'secret' */`) are
+    /// redacted too.
+    ///
+    /// Everything else is kept as is, including the original whitespace. The leading comments are not part of the SQL
+    /// of the statement (see [`Statement::comments`]). When `redact_comments` is enabled, the content
+    /// of comments is replaced by the placeholder but the comment delimiters are kept (ex: `/* ? */`) so the comments
+    /// remain comments.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use loose_sqlparser::{loose_sqlparse, RedactOptions};
+    /// let sql = "SELECT * FROM users\n WHERE email = 'john@doe.com' AND age > 42 -- John";
+    /// let stmt = loose_sqlparse(sql).next().unwrap();
+    /// assert_eq!(
+    ///     stmt.redact(&RedactOptions::default()),
+    ///     "SELECT * FROM users\n WHERE email = ? AND age > ? -- John"
+    /// );
+    /// let options = RedactOptions { placeholder: "***".to_string(), redact_comments: true, ..Default::default() };
+    /// assert_eq!(stmt.redact(&options), "SELECT * FROM users\n WHERE email = *** AND age > *** -- ***");
+    /// ```
+    pub fn redact(&self, options: &RedactOptions) -> String {
+        let mut edits = self.edits();
+        for token in leaves(&self.tokens) {
+            if let Some(replacement) = redaction(token, options) {
+                edits.replace_token(token, replacement).expect("tokens do not overlap");
+            }
+        }
+        edits.apply().sql
+    }
+}
+
+// Returns the text replacing the given token, `None` if the token is kept as is.
+fn redaction(token: &Token, options: &RedactOptions) -> Option<String> {
+    let replacement = match token.value {
+        TokenValue::NumericConstant(_) => options.placeholder.clone(),
+        TokenValue::QuotedIdentifierOrConstant(_) if is_string_constant(token) => options.placeholder.clone(),
+        TokenValue::CopyData(data) if data.ends_with("\\.") => format!("{}\n\\.", options.placeholder),
+        TokenValue::CopyData(_) => options.placeholder.clone(),
+        TokenValue::Comment(comment) if options.redact_comments => {
+            if comment.starts_with("/*") {
+                let end = if comment.len() > 3 && comment.ends_with("*/") { " */" } else { "" };
+                format!("/* {}{}", options.placeholder, end)
+            } else if comment.starts_with("--") {
+                format!("-- {}", options.placeholder)
+            } else {
+                format!("# {}", options.placeholder)
+            }
+        }
+        TokenValue::OptimizerHint(comment) | TokenValue::ExecutableComment(comment) => {
+            // The content of a hint or of an executable comment is SQL, only its literals are redacted (when executable
+            // comments are tokenized, the `/*!50001` and `*/` tokens have no content).
+            let prefix = ["/*+", "/*!", "/*M!"].into_iter().find(|prefix| comment.starts_with(prefix))?;
+            let start = prefix.len() + comment[prefix.len()..].chars().take_while(|c| c.is_ascii_digit()).count();
+            let has_end = comment.len() >= start + 2 && comment.ends_with("*/");
+            let end = if has_end { comment.len() - 2 } else { comment.len() };
+            let content = redact_literals(&comment[start..end], options);
+            if content == comment[start..end] {
+                return None;
+            }
+            format!("{}{}{}", &comment[..start], content, &comment[end..])
+        }
+        _ => return None,
+    };
+    Some(replacement)
+}
+
+// Returns the given SQL with its literals redacted, see `redaction`.
+fn redact_literals(sql: &str, options: &RedactOptions) -> String {
+    let mut edits = EditSet::new(sql);
+    for statement in loose_sqlparse_with_options(sql, options.parse_options.clone()) {
+        for token in leaves(&statement.tokens) {
+            if let Some(replacement) = redaction(token, options) {
+                edits.replace_token(token, replacement).expect("tokens do not overlap");
+            }
+        }
+    }
+    edits.apply().sql
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{loose_sqlparse, loose_sqlparse_with_options, Dialect, Options, RedactOptions};
+
+    fn redact(sql: &str, options: &RedactOptions) -> String {
+        loose_sqlparse(sql).next().unwrap().redact(options)
+    }
+
+    #[test]
+    fn test_redact_literals() {
+        let options = RedactOptions::default();
+        assert_eq!(
+            redact("INSERT INTO \"users\" (name, age)\n\tVALUES ('O''Reilly', -4.2e1), (E'x\\n', 0x1F);", &options),
+            "INSERT INTO \"users\" (name, age)\n\tVALUES (?, -?), (?, ?);"
+        );
+        assert_eq!(redact("SELECT $$secret$$, N'こんにちは', B'101'", &options), "SELECT ?, ?, ?");
+        assert_eq!(
+            redact("SELECT a FROM t WHERE b = :b AND c = ?", &options),
+            "SELECT a FROM t WHERE b = :b AND c = ?"
+        );
+        assert_eq!(redact("SELECT 'unterminated", &options), "SELECT ?");
+        assert_eq!(redact("SELECT (1, 'unclosed'", &options), "SELECT (?, ?");
+        assert_eq!(redact("SELECT 1 /* one */", &options), "SELECT ? /* one */");
+    }
+
+    #[test]
+    fn test_redact_comments() {
+        let options = RedactOptions { placeholder: "***".to_string(), redact_comments: true, ..Default::default() };
+        assert_eq!(
+            redact("/* leading */ SELECT /* inner */ 1 -- one\n# two\n, 2 /* unterminated", &options),
+            "SELECT /* *** */ *** -- ***\n# ***\n, *** /* ***"
+        );
+    }
+
+    #[test]
+    fn test_redact_hints_and_executable_comments() {
+        let options = RedactOptions::default();
+        let sql = "SELECT /*+ SET_VAR(x = 'a') */ 1 /*!50001 , 'secret' */ /*M!100100 , 2*/ /* 'kept' */";
+        let expected = "SELECT /*+ SET_VAR(x = ?) */ ? /*!50001 , ? */ /*M!100100 , ?*/ /* 'kept' */";
+        assert_eq!(redact(sql, &options), expected);
+        let tokenized = Options { tokenize_executable_comments: true, ..Default::default() };
+        let stmt = loose_sqlparse_with_options(sql, tokenized).next().unwrap();
+        assert_eq!(stmt.redact(&options), expected);
+        assert_eq!(redact("SELECT /*!'unterminated", &options), "SELECT /*!?");
+
+        // The content is tokenized with the options of the statement.
+        let mysql = Options { dialect: Dialect::MySql, ..Default::default() };
+        let options = RedactOptions { parse_options: mysql.clone(), ..Default::default() };
+        let sql = "SELECT /*!50000 'It\\'s secret', 'a\\\\' */ 1";
+        let stmt = loose_sqlparse_with_options(sql, mysql).next().unwrap();
+        assert_eq!(stmt.redact(&options), "SELECT /*!50000 ?, ? */ ?");
+    }
+}
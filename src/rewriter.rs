@@ -0,0 +1,219 @@
+use crate::tokens::span;
+use crate::{Error, Statement, Token};
+use std::ops::Range;
+
+/// A set of edits (insertions, deletions and replacements) to apply to a SQL input.
+///
+/// Edits are keyed by byte offsets of the original input (or by tokens) and are all applied at once by
+/// [`EditSet::apply`], so the offsets of the tokens remain valid while the edits are collected. Edits must not overlap,
+/// but several insertions can be made at the same offset: they are applied in the order they were added.
+///
+/// # Examples
+///
+/// ```rust
+/// use loose_sqlparser::loose_sqlparse;
+/// let stmt = loose_sqlparse("SELECT a FROM t WHERE b = 1").next().unwrap();
+/// let tokens = stmt.tokens();
+/// let mut edits = stmt.edits();
+/// edits.replace_token(&tokens[1], "a, b").unwrap();
+/// edits.insert_after(&tokens[3], " AS x").unwrap();
+/// edits.delete(15..27).unwrap();
+/// assert!(edits.replace_token(&tokens[5], "c").is_err());
+///
+/// let rewritten = edits.apply();
+/// assert_eq!(rewritten.sql, "SELECT a, b FROM t AS x");
+/// assert_eq!(rewritten.offsets.map(14), Some(17));
+/// ```
+#[derive(Debug, Clone)]
+pub struct EditSet<'s> {
+    input: &'s str,
+    range: Range<usize>,
+    // The edits sorted by range, each one with its replacement text.
+    edits: Vec<(Range<usize>, String)>,
+}
+
+/// The result of [`EditSet::apply`].
+#[derive(Debug, Clone)]
+pub struct Rewritten {
+    /// The rewritten SQL.
+    pub sql: String,
+
+    /// The mapping of the offsets of the original input to the offsets of the rewritten SQL.
+    pub offsets: OffsetMap,
+}
+
+/// A mapping of the byte offsets of an input to the byte offsets of its rewritten version, see [`EditSet::apply`].
+#[derive(Debug, Clone)]
+pub struct OffsetMap {
+    range: Range<usize>,
+    // The applied edits: the original range and the length of the replacement text.
+    edits: Vec<(Range<usize>, usize)>,
+}
+
+impl<'s> EditSet<'s> {
+    /// Creates an empty set of edits of the given input.
+    pub fn new(input: &'s str) -> Self {
+        Self { input, range: 0..input.len(), edits: Vec::new() }
+    }
+
+    /// Returns whether the set contains no edits.
+    pub fn is_empty(&self) -> bool {
+        self.edits.is_empty()
+    }
+
+    /// Inserts a text at the given offset.
+    pub fn insert(&mut self, offset: usize, text: impl Into<String>) -> Result<(), Error> {
+        self.replace(offset..offset, text)
+    }
+
+    /// Deletes the given range of the input.
+    pub fn delete(&mut self, range: Range<usize>) -> Result<(), Error> {
+        self.replace(range, "")
+    }
+
+    /// Replaces the given range of the input with a text.
+    ///
+    /// Returns an error if the range is not a valid range of the input or if it overlaps a range already edited.
+    pub fn replace(&mut self, range: Range<usize>, text: impl Into<String>) -> Result<(), Error> {
+        let is_valid = range.start <= range.end
+            && self.range.start <= range.start
+            && range.end <= self.range.end
+            && self.input.is_char_boundary(range.start)
+            && self.input.is_char_boundary(range.end);
+        if !is_valid {
+            return Err(Error::InvalidEditRange { range });
+        }
+        // An insertion only conflicts with an edit strictly containing its offset, other edits conflict as soon as
+        // they share a byte.
+        let overlaps = |edit: &Range<usize>| {
+            if range.is_empty() || edit.is_empty() {
+                (edit.start < range.start && range.start < edit.end)
+                    || (range.start < edit.start && edit.start < range.end)
+            } else {
+                edit.start < range.end && range.start < edit.end
+            }
+        };
+        if let Some((edit, _)) = self.edits.iter().find(|(edit, _)| overlaps(edit)) {
+            return Err(Error::OverlappingEdits { first: edit.clone(), second: range });
+        }
+        // Keep the edits sorted, after the edits with the same range (insertions at the same offset keep their order).
+        let index = self.edits.partition_point(|(edit, _)| (edit.start, edit.end) <= (range.start, range.end));
+        self.edits.insert(index, (range, text.into()));
+        Ok(())
+    }
+
+    /// Inserts a text before a token.
+    pub fn insert_before(&mut self, token: &Token, text: impl Into<String>) -> Result<(), Error> {
+        self.insert(token_range(token).start, text)
+    }
+
+    /// Inserts a text after a token.
+    pub fn insert_after(&mut self, token: &Token, text: impl Into<String>) -> Result<(), Error> {
+        self.insert(token_range(token).end, text)
+    }
+
+    /// Deletes a token.
+    pub fn delete_token(&mut self, token: &Token) -> Result<(), Error> {
+        self.delete(token_range(token))
+    }
+
+    /// Replaces a token with a text.
+    pub fn replace_token(&mut self, token: &Token, text: impl Into<String>) -> Result<(), Error> {
+        self.replace(token_range(token), text)
+    }
+
+    /// Applies the edits and returns the rewritten SQL.
+    pub fn apply(self) -> Rewritten {
+        let mut sql = String::with_capacity(self.range.len());
+        let mut offset = self.range.start;
+        let mut edits = Vec::with_capacity(self.edits.len());
+        for (range, text) in self.edits {
+            sql.push_str(&self.input[offset..range.start]);
+            sql.push_str(&text);
+            offset = range.end;
+            edits.push((range, text.len()));
+        }
+        sql.push_str(&self.input[offset..self.range.end]);
+        Rewritten { sql, offsets: OffsetMap { range: self.range, edits } }
+    }
+}
+
+impl OffsetMap {
+    /// Returns the offset in the rewritten SQL corresponding to the given offset of the original input.
+    ///
+    /// An offset located in a deleted or replaced range is mapped to the start of its replacement, and an offset where
+    /// texts were inserted is mapped after the inserted texts. Returns `None` if the offset is outside of the rewritten
+    /// range of the input.
+    pub fn map(&self, offset: usize) -> Option<usize> {
+        if !self.range.contains(&offset) && offset != self.range.end {
+            return None;
+        }
+        let mut new_offset = offset - self.range.start;
+        for (range, len) in &self.edits {
+            if range.start > offset || (range.start == offset && !range.is_empty()) {
+                break;
+            }
+            if range.end > offset {
+                // The offset is inside an edited range.
+                return Some(new_offset - (offset - range.start));
+            }
+            new_offset = new_offset + len - range.len();
+        }
+        Some(new_offset)
+    }
+}
+
+impl<'s> Statement<'s> {
+    /// Returns an empty set of edits of the statement.
+    ///
+    /// The offsets of the edits are the offsets of the tokens of the statement and must be in the range of the
+    /// statement. The rewritten SQL only contains the SQL of the statement.
+    pub fn edits(&self) -> EditSet<'s> {
+        EditSet { input: self.input, range: self.start().offset..self.end().offset, edits: Vec::new() }
+    }
+}
+
+// Returns the range of the input covered by a token.
+fn token_range(token: &Token) -> Range<usize> {
+    span(std::slice::from_ref(token)).unwrap_or(token.start.offset..token.end.offset)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{EditSet, Error};
+
+    #[test]
+    fn test_edit_set() {
+        let mut edits = EditSet::new("SELECT 1, 2");
+        edits.insert(7, "0, ").unwrap();
+        edits.insert(7, "(").unwrap();
+        edits.replace(7..8, "one").unwrap();
+        edits.insert(8, ")").unwrap();
+        edits.delete(8..11).unwrap();
+        edits.insert(11, ";").unwrap();
+        let rewritten = edits.apply();
+        assert_eq!(rewritten.sql, "SELECT 0, (one);");
+        assert_eq!(rewritten.offsets.map(0), Some(0));
+        assert_eq!(rewritten.offsets.map(7), Some(11));
+        assert_eq!(rewritten.offsets.map(8), Some(15));
+        assert_eq!(rewritten.offsets.map(10), Some(15));
+        assert_eq!(rewritten.offsets.map(11), Some(16));
+        assert_eq!(rewritten.offsets.map(12), None);
+    }
+
+    #[test]
+    fn test_edit_set_errors() {
+        let mut edits = EditSet::new("SELECT 'é'");
+        edits.replace(7..11, "?").unwrap();
+        assert!(matches!(edits.insert(9, "x"), Err(Error::InvalidEditRange { .. })));
+        assert!(matches!(edits.insert(20, "x"), Err(Error::InvalidEditRange { .. })));
+        assert!(matches!(
+            edits.insert(10, "x"),
+            Err(Error::OverlappingEdits { first, second }) if first == (7..11) && second == (10..10)
+        ));
+        assert!(matches!(edits.delete(0..8), Err(Error::OverlappingEdits { .. })));
+        edits.delete(0..7).unwrap();
+        edits.insert(11, "x").unwrap();
+        assert_eq!(edits.apply().sql, "?x");
+    }
+}
@@ -0,0 +1,345 @@
+use crate::tokens::{is_comment_or_hint, next_significant, span};
+use crate::{Statement, Token, TokenValue};
+
+// Keywords ending the select list when found at the top level of the statement.
+const SELECT_LIST_TERMINATORS: [&str; 16] = [
+    "FROM",
+    "INTO",
+    "WHERE",
+    "GROUP",
+    "HAVING",
+    "WINDOW",
+    "QUALIFY",
+    "ORDER",
+    "LIMIT",
+    "OFFSET",
+    "FETCH",
+    "UNION",
+    "INTERSECT",
+    "EXCEPT",
+    "MINUS",
+    "FOR",
+];
+
+// Modifiers that may follow the `SELECT` keyword and are not part of the first select item.
+const SELECT_MODIFIERS: [&str; 10] = [
+    "ALL",
+    "DISTINCTROW",
+    "HIGH_PRIORITY",
+    "STRAIGHT_JOIN",
+    "SQL_SMALL_RESULT",
+    "SQL_BIG_RESULT",
+    "SQL_BUFFER_RESULT",
+    "SQL_NO_CACHE",
+    "SQL_CALC_FOUND_ROWS",
+    "SQL_CACHE",
+];
+
+// Keywords that can end an expression and should not be mistaken for an implicit alias (ex: `CASE ... END`,
+// `x IS NOT NULL`).
+const NOT_AN_ALIAS: [&str; 6] = ["END", "NULL", "TRUE", "FALSE", "UNKNOWN", "DISTINCT"];
+
+// Keywords expecting an operand, the identifier following them is not an implicit alias (ex: `active` in
+// `SELECT NOT active`).
+const NOT_BEFORE_AN_ALIAS: [&str; 24] = [
+    "NOT", "INTERVAL", "DISTINCT", "CASE", "WHEN", "THEN", "ELSE", "AND", "OR", "XOR", "IS", "IN", "LIKE", "ILIKE",
+    "BETWEEN", "EXISTS", "ANY", "ALL", "SOME", "COLLATE", "ESCAPE", "TO", "DIV", "MOD",
+];
+
+/// The alias of a select item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelectAlias<'s> {
+    /// An alias introduced by the `AS` keyword (ex: `SELECT a + b AS total`).
+    Explicit(&'s str),
+
+    /// An alias without the `AS` keyword (ex: `SELECT a + b total`).
+    Implicit(&'s str),
+
+    /// A wildcard (`*`) with its optional qualifier (ex: `t` for `SELECT t.*`).
+    Wildcard(Option<&'s str>),
+}
+
+/// An item of the select list of a statement.
+#[derive(Debug)]
+pub struct SelectItem<'a, 's> {
+    /// The tokens of the item (the expression and its alias, not including the separating comma).
+    pub tokens: &'a [Token<'s>],
+
+    /// The text of the expression (without the alias).
+    pub expression: &'s str,
+
+    /// The alias of the item if any.
+    pub alias: Option<SelectAlias<'s>>,
+}
+
+impl<'s> SelectItem<'_, 's> {
+    /// The name of the column produced by the item if it can be determined.
+    ///
+    /// This is the alias of the item if any, or the last identifier of a column reference (ex: `name` for
+    /// `SELECT u.name`). Quoted aliases and identifiers are returned as is, including their quotes.
+    pub fn name(&self) -> Option<&'s str> {
+        match self.alias {
+            Some(SelectAlias::Explicit(alias)) | Some(SelectAlias::Implicit(alias)) => Some(alias),
+            Some(SelectAlias::Wildcard(_)) => None,
+            None => {
+                // A column reference: `name`, `table.name`, `schema.table.name`, ...
+                let mut significant = self.tokens.iter().filter(|t| !is_comment_or_hint(t));
+                let is_column_reference = significant.clone().enumerate().all(|(i, t)| match i % 2 {
+                    0 => Self::identifier(t).is_some(),
+                    _ => matches!(t.value, TokenValue::Any(".")),
+                });
+                significant.next_back().filter(|_| is_column_reference).and_then(Self::identifier)
+            }
+        }
+    }
+
+    // Returns the text of the token if it's an identifier or a quoted identifier.
+    fn identifier(token: &Token<'s>) -> Option<&'s str> {
+        match token.value {
+            TokenValue::IdentifierOrKeyword(value) => Some(value),
+            TokenValue::QuotedIdentifierOrConstant(value) if !value.starts_with('\'') => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl<'s> Statement<'s> {
+    /// Returns the items of the select list of the first top-level `SELECT` of the statement.
+    ///
+    /// Items are split on top-level commas, commas found in parentheses (function calls, sub-queries, ...) are not
+    /// considered. Returns an empty list if the statement does not have a top-level `SELECT`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use loose_sqlparser::{loose_sqlparse, SelectAlias};
+    /// let stmt = loose_sqlparse("SELECT id, count(*) AS total, u.* FROM users u").next().unwrap();
+    /// let items = stmt.select_items();
+    /// assert_eq!(items.len(), 3);
+    /// assert_eq!(items[0].expression, "id");
+    /// assert_eq!(items[1].expression, "count(*)");
+    /// assert_eq!(items[1].alias, Some(SelectAlias::Explicit("total")));
+    /// assert_eq!(items[2].alias, Some(SelectAlias::Wildcard(Some("u"))));
+    /// ```
+    pub fn select_items(&self) -> Vec<SelectItem<'_, 's>> {
+        let tokens: &[Token<'s>] = &self.tokens;
+        let mut items = Vec::new();
+        let Some(select) = tokens.iter().position(|t| t.is_keyword("SELECT")) else {
+            return items;
+        };
+
+        // Skip the modifiers following the SELECT keyword.
+        let mut index = next_significant(tokens, select + 1).unwrap_or(tokens.len());
+        while index < tokens.len() {
+            let token = &tokens[index];
+            if token.is_keyword("DISTINCT") {
+                // DISTINCT ON (...) (PostgreSQL)
+                let next = next_significant(tokens, index + 1).unwrap_or(tokens.len());
+                index = if next < tokens.len() && tokens[next].is_keyword("ON") {
+                    Self::skip_parenthesized(tokens, next_significant(tokens, next + 1).unwrap_or(tokens.len()))
+                } else {
+                    next
+                };
+                continue;
+            } else if token.is_keyword("TOP") {
+                // TOP n [PERCENT] [WITH TIES] (SQL Server)
+                index = Self::skip_parenthesized(tokens, next_significant(tokens, index + 1).unwrap_or(tokens.len()));
+                if index < tokens.len() && tokens[index].is_keyword("PERCENT") {
+                    index = next_significant(tokens, index + 1).unwrap_or(tokens.len());
+                }
+                if index < tokens.len() && tokens[index].is_keyword("WITH") {
+                    // TIES
+                    index = next_significant(tokens, index + 1).unwrap_or(tokens.len());
+                    index = next_significant(tokens, index + 1).unwrap_or(tokens.len());
+                }
+                continue;
+            } else if !SELECT_MODIFIERS.iter().any(|modifier| token.is_keyword(modifier)) {
+                break;
+            }
+            index = next_significant(tokens, index + 1).unwrap_or(tokens.len());
+        }
+
+        // Find the end of the select list.
+        let end = (index..tokens.len())
+            .find(|&i| {
+                tokens[i].is_statement_delimiter()
+                    || SELECT_LIST_TERMINATORS.iter().any(|keyword| tokens[i].is_keyword(keyword))
+            })
+            .unwrap_or(tokens.len());
+
+        // Split the select list on top-level commas.
+        let mut start = index;
+        for i in index..=end {
+            if i == end || tokens[i].is_comma() {
+                if let Some(item) = self.select_item(&tokens[start..i]) {
+                    items.push(item);
+                }
+                start = i + 1;
+            }
+        }
+        items
+    }
+
+    // Build a select item from its tokens (leading and trailing comments are ignored).
+    fn select_item<'a>(&self, tokens: &'a [Token<'s>]) -> Option<SelectItem<'a, 's>> {
+        let first = tokens.iter().position(|t| !is_comment_or_hint(t))?;
+        let last = tokens.iter().rposition(|t| !is_comment_or_hint(t))?;
+        let tokens = &tokens[first..=last];
+        let significant: Vec<usize> = (0..tokens.len()).filter(|&i| !is_comment_or_hint(&tokens[i])).collect();
+        let last_token = &tokens[tokens.len() - 1];
+        let before_last = significant.len().checked_sub(2).map(|i| &tokens[significant[i]]);
+        let before_before_last = significant.len().checked_sub(3).map(|i| &tokens[significant[i]]);
+
+        let (expression_end, alias) = if matches!(last_token.value, TokenValue::Operator("*"))
+            && before_last.is_none_or(|t| matches!(t.value, TokenValue::Any(".")))
+        {
+            // Wildcard: `*` or `qualifier.*`
+            let qualifier = match significant.len() {
+                0..=2 => None,
+                n => span(&tokens[..significant[n - 2]]).map(|range| &self.input[range]),
+            };
+            (tokens.len(), Some(SelectAlias::Wildcard(qualifier)))
+        } else if significant.len() >= 3 && before_last.is_some_and(|t| t.is_keyword("AS")) {
+            // Explicit alias: `expression AS alias`
+            let alias = SelectItem::identifier(last_token).or(match last_token.value {
+                TokenValue::QuotedIdentifierOrConstant(value) => Some(value),
+                _ => None,
+            })?;
+            (significant[significant.len() - 2], Some(SelectAlias::Explicit(alias)))
+        } else if significant.len() >= 2
+            && before_last.is_some_and(|t| {
+                (t.is_identifier_or_keyword() && !NOT_BEFORE_AN_ALIAS.iter().any(|keyword| t.is_keyword(keyword)))
+                    || t.is_quoted_identifier_or_constant()
+                    || t.is_numeric_constant()
+                    || t.is_parameter_marker()
+                    || t.is_parenthesis()
+            })
+            // The unit of an interval is not an alias (ex: `DAY` in `INTERVAL 1 DAY`).
+            && !before_before_last.is_some_and(|t| t.is_keyword("INTERVAL"))
+        {
+            // Implicit alias: `expression alias`
+            match SelectItem::identifier(last_token) {
+                Some(alias) if !NOT_AN_ALIAS.iter().any(|keyword| last_token.is_keyword(keyword)) => {
+                    (significant[significant.len() - 1], Some(SelectAlias::Implicit(alias)))
+                }
+                _ => (tokens.len(), None),
+            }
+        } else {
+            (tokens.len(), None)
+        };
+
+        let expression = span(&tokens[..expression_end]).map(|range| &self.input[range])?;
+        Some(SelectItem { tokens, expression, alias })
+    }
+
+    // Skip the token at `index` and the parenthesized block following it if the token is an opening parenthesis.
+    // Returns the index of the next significant token.
+    fn skip_parenthesized(tokens: &[Token], index: usize) -> usize {
+        if index >= tokens.len() {
+            return index;
+        }
+        let mut next = index + 1;
+        if matches!(tokens[index].value, TokenValue::Any("(")) {
+            while next < tokens.len()
+                && (tokens[next].is_fragment() || matches!(tokens[next].value, TokenValue::Any(")")))
+            {
+                let closing = tokens[next].is_parenthesis();
+                next += 1;
+                if closing {
+                    break;
+                }
+            }
+        }
+        next_significant(tokens, next).unwrap_or(tokens.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{loose_sqlparse, SelectAlias};
+
+    #[test]
+    fn test_select_items() {
+        let stmt = loose_sqlparse(
+            "SELECT a, t.b, c AS \"C\", d e, f + (1, 2) AS g, CASE WHEN x THEN 1 END, x IS NOT NULL, *, s.t.* FROM t",
+        )
+        .next()
+        .unwrap();
+        let items = stmt.select_items();
+        assert_eq!(
+            items.iter().map(|i| i.expression).collect::<Vec<_>>(),
+            ["a", "t.b", "c", "d", "f + (1, 2)", "CASE WHEN x THEN 1 END", "x IS NOT NULL", "*", "s.t.*"]
+        );
+        assert_eq!(
+            items.iter().map(|i| i.alias).collect::<Vec<_>>(),
+            [
+                None,
+                None,
+                Some(SelectAlias::Explicit("\"C\"")),
+                Some(SelectAlias::Implicit("e")),
+                Some(SelectAlias::Explicit("g")),
+                None,
+                None,
+                Some(SelectAlias::Wildcard(None)),
+                Some(SelectAlias::Wildcard(Some("s.t"))),
+            ]
+        );
+        assert_eq!(
+            items.iter().map(|i| i.name()).collect::<Vec<_>>(),
+            [Some("a"), Some("b"), Some("\"C\""), Some("e"), Some("g"), None, None, None, None]
+        );
+        assert_eq!(items[4].tokens.len(), 7);
+    }
+
+    #[test]
+    fn test_select_items_keyword_operators() {
+        fn aliases(sql: &str) -> Vec<Option<SelectAlias<'_>>> {
+            loose_sqlparse(sql).next().unwrap().select_items().iter().map(|i| i.alias).collect()
+        }
+        assert_eq!(aliases("SELECT NOT active, INTERVAL 1 DAY, INTERVAL '1' HOUR FROM t"), [None, None, None]);
+        assert_eq!(aliases("SELECT CASE WHEN a THEN b ELSE c END FROM t"), [None]);
+        assert_eq!(aliases("SELECT a AND b, a OR b, a IS NULL, a IS NOT b, DISTINCT a FROM t"), [None; 5]);
+        assert_eq!(
+            aliases("SELECT NOT active inactive, INTERVAL 1 DAY delay FROM t"),
+            [Some(SelectAlias::Implicit("inactive")), Some(SelectAlias::Implicit("delay"))]
+        );
+    }
+
+    #[test]
+    fn test_select_items_modifiers() {
+        let items = |sql: &str| {
+            loose_sqlparse(sql)
+                .next()
+                .unwrap()
+                .select_items()
+                .iter()
+                .map(|i| i.expression.to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(items("SELECT DISTINCT a, b FROM t"), ["a", "b"]);
+        assert_eq!(items("SELECT DISTINCT ON (a) a, b FROM t"), ["a", "b"]);
+        assert_eq!(items("SELECT TOP 10 a FROM t"), ["a"]);
+        assert_eq!(items("SELECT TOP (10) PERCENT WITH TIES a FROM t"), ["a"]);
+        assert_eq!(items("SELECT SQL_NO_CACHE ALL a FROM t"), ["a"]);
+    }
+
+    #[test]
+    fn test_select_items_statements() {
+        let items = |sql: &str| {
+            loose_sqlparse(sql)
+                .next()
+                .unwrap()
+                .select_items()
+                .iter()
+                .map(|i| i.expression.to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(items("WITH x AS (SELECT 1 AS a) SELECT a /* first */, /* second */ 2 FROM x"), ["a", "2"]);
+        assert_eq!(items("INSERT INTO t SELECT 1, 2 FROM dual"), ["1", "2"]);
+        assert_eq!(items("SELECT 1, 2;"), ["1", "2"]);
+        assert_eq!(items("SELECT 1 UNION SELECT 2"), ["1"]);
+        assert_eq!(items("SELECT count(*) INTO n FROM t"), ["count(*)"]);
+        assert!(items("DELETE FROM t").is_empty());
+        assert!(items("SELECT FROM t").is_empty());
+    }
+}
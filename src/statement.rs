@@ -1,4 +1,5 @@
-use crate::{tokens::Tokens, Position, TokenValue};
+use crate::tokens::{first_leaf, last_leaf, Tokens};
+use crate::{Error, Position, TokenValue};
 
 #[cfg(feature = "serialize")]
 use serde::Serialize;
@@ -10,30 +11,47 @@ pub struct Statement<'s> {
     // The input from which the statement was parsed.
     pub(crate) input: &'s str,
 
+    // The comments preceding the statement, they are not part of its tokens.
+    #[cfg_attr(feature = "serialize", serde(skip_serializing_if = "<[_]>::is_empty"))]
+    pub(crate) leading: Tokens<'s>,
+
     // All tokens found in the statement.
     pub(crate) tokens: Tokens<'s>,
+
+    // The problems found by the tokenizer.
+    #[cfg_attr(feature = "serialize", serde(skip))]
+    pub(crate) diagnostics: Vec<Error>,
 }
 
-impl Statement<'_> {
+impl<'s> Statement<'s> {
     /// The SQL statement.
     pub fn sql(&self) -> &str {
-        return &self.input[self.start().offset..self.end().offset];
+        &self.input[self.start().offset..self.end().offset]
     }
 
     /// The start position of the statement.
     pub fn start(&self) -> &Position {
-        &self.tokens[0].start
+        // The positions of fragments are not reliable, the positions of the tokens they contain are used instead.
+        first_leaf(&self.tokens).map_or(&self.tokens[0].start, |token| &token.start)
     }
 
     /// The column where the statement starts.
     pub fn end(&self) -> &Position {
-        &self.tokens[self.tokens.len() - 1].end
+        last_leaf(&self.tokens).map_or(&self.tokens[self.tokens.len() - 1].end, |token| &token.end)
     }
 
     pub fn tokens(&self) -> &Tokens<'_> {
         &self.tokens
     }
 
+    /// The problems found by the tokenizer in the statement (ex: [`Error::MaxDepthExceeded`]).
+    ///
+    /// The tokenizer never fails, the statement is always tokenized, but the diagnostics tell where its structure may
+    /// differ from what was expected.
+    pub fn diagnostics(&self) -> &[Error] {
+        &self.diagnostics
+    }
+
     /// The list of keywords found in the statement at the top level.
     /// Keywords found on CTEs or sub queries are not included in this list.
     pub fn keywords(&self) -> Vec<&str> {
@@ -54,6 +72,28 @@ impl Statement<'_> {
         self.tokens.iter().all(|t| t.is_comment() || t.is_statement_delimiter())
     }
 
+    /// Returns whether the statement is a psql meta-command (ex: `\connect db`), see [`crate::Options::psql`].
+    pub fn is_meta_command(&self) -> bool {
+        self.tokens.iter().any(|t| t.is_meta_command())
+    }
+
+    /// Returns the inline data of a `COPY ... FROM STDIN` statement, without the final `\.` line.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use loose_sqlparser::loose_sqlparse;
+    /// let mut statements = loose_sqlparse("COPY t (a, b) FROM stdin;\n1\tone\n2\ttwo\n\\.\nSELECT 1;");
+    /// assert_eq!(statements.next().unwrap().copy_data(), Some("1\tone\n2\ttwo\n"));
+    /// assert_eq!(statements.next().unwrap().sql(), "SELECT 1;");
+    /// ```
+    pub fn copy_data(&self) -> Option<&'s str> {
+        self.tokens.iter().find_map(|t| match t.value {
+            TokenValue::CopyData(data) => Some(data.strip_suffix("\\.").unwrap_or(data)),
+            _ => None,
+        })
+    }
+
     /// Returns whether the statement may return rows.
     ///
     /// Used to categorize statements that return rows versus those that don't return rows.
@@ -102,4 +142,11 @@ mod tests {
         assert!(statements[2].is_empty());
         assert!(!statements[3].is_empty());
     }
+
+    #[test]
+    fn test_statement_unclosed_parenthesis() {
+        let statement = loose_sqlparse("SELECT (1, (2").next().unwrap();
+        assert_eq!(statement.sql(), "SELECT (1, (2");
+        assert_eq!(statement.end().column, 13);
+    }
 }
@@ -1,13 +1,75 @@
-use crate::{Options, Position, Statement};
-use crate::{Token, TokenValue, Tokens};
+use crate::{Dialect, Error, Options, Position, QuestionMarkMode, Statement};
+use crate::{FragmentKind, Token, TokenValue, Tokens, VariableScope};
 
-// The list of all operators supported by the tokenizer.
-// The tokenizer will try to match the longest operator possible, so that list must be sorted by the length descending.
+// The operators supported by all the dialects.
 const OPERATORS: [&str; 24] = [
     "!~*", "!=", ">=", "<=", "<>", "||", "<<", ">>", "::", "~*", "!~", "+", "-", "*", "/", "=", ">", "<", "!", "%",
     "~", "&", "|", "^",
 ];
 
+// The operators starting with a question mark (PostgreSQL), see `QuestionMarkMode`.
+// See: https://www.postgresql.org/docs/current/functions-json.html and
+// https://www.postgresql.org/docs/current/functions-geometry.html
+const QUESTION_MARK_OPERATORS: [&str; 7] = ["?-|", "?||", "?|", "?&", "?-", "?#", "?"];
+
+// Returns the kind of the block opened by the given character.
+fn bracket_kind(c: char) -> Option<FragmentKind> {
+    match c {
+        '(' => Some(FragmentKind::Parentheses),
+        '[' => Some(FragmentKind::Brackets),
+        '{' => Some(FragmentKind::Braces),
+        _ => None,
+    }
+}
+
+// Returns the scope of a system variable prefixed by an explicit scope (ex: `@@session` or `@@session.sql_mode`).
+fn system_variable_scope(value: &str) -> Option<VariableScope> {
+    let scope = value.strip_prefix("@@")?.split('.').next()?;
+    if scope.eq_ignore_ascii_case("global") {
+        Some(VariableScope::Global)
+    } else if scope.eq_ignore_ascii_case("session") || scope.eq_ignore_ascii_case("local") {
+        Some(VariableScope::Session)
+    } else {
+        None
+    }
+}
+
+// Returns the token of a parameter marker (`?`, `$1`, `:name`...), unless it's a variable of the dialect.
+fn parameter_marker_or_variable(dialect: Dialect, value: &str) -> TokenValue<'_> {
+    let scope = match (dialect, value.as_bytes()) {
+        (_, [b'@', b'@', _, ..]) if value.contains('.') => system_variable_scope(value),
+        (_, [b'@', b'@', _, ..]) => Some(VariableScope::System),
+        (Dialect::MySql, [b'@', _, ..]) => Some(VariableScope::User),
+        (Dialect::SqlServer, [b'@', _, ..]) => Some(VariableScope::Local),
+        (Dialect::Snowflake | Dialect::DuckDb, [b'$', name @ ..]) if name.iter().any(|c| !c.is_ascii_digit()) => {
+            Some(VariableScope::Session)
+        }
+        _ => None,
+    };
+    match scope {
+        Some(scope) => TokenValue::Variable(value, scope),
+        None => TokenValue::ParameterMarker(value),
+    }
+}
+
+// Returns the operators of a dialect, in addition to the common `OPERATORS`.
+fn dialect_operators(dialect: Dialect) -> &'static [&'static str] {
+    match dialect {
+        Dialect::Generic => &[],
+        // See: https://www.postgresql.org/docs/current/functions.html
+        Dialect::PostgreSql => &[
+            "->", "->>", "#>", "#>>", "#-", "#", "@>", "<@", "&&", "-|-", "<->", "@@", "@?", "!!", "||/", "|/", ":=",
+            "=>",
+        ],
+        Dialect::MySql => &["->", "->>", ":=", "<=>", "&&"],
+        Dialect::Sqlite => &["->", "->>"],
+        Dialect::DuckDb => &["->", "->>", "@>", "<@", "&&", "**", "//", ":=", "=>", "^@"],
+        Dialect::Oracle => &["=>", ":=", "**"],
+        Dialect::SqlServer => &[],
+        Dialect::Snowflake => &["->", "->>", "=>"],
+    }
+}
+
 pub(crate) struct Tokenizer<'s> {
     // The input to be tokenized (the whole SQL to be tokenized).
     input: &'s str,
@@ -29,12 +91,35 @@ pub(crate) struct Tokenizer<'s> {
 
     // The tokenizer options.
     options: Options,
+
+    // The number of executable comments (`/*! ... */`) being tokenized (see `Options::tokenize_executable_comments`).
+    executable_comment_depth: usize,
+
+    // The closing characters of the blocks being captured, the innermost last (ex: `[')', ']']` in `(a[`).
+    closing_chars: Vec<char>,
+
+    // The closing characters of the blocks nested deeper than `Options::max_depth`, which are tokenized flat.
+    flat_closing_chars: Vec<char>,
+
+    // The problems found while tokenizing the current statement, see `Statement::diagnostics`.
+    diagnostics: Vec<Error>,
+
+    // The operators of the dialect (including the custom operators of the options) sorted by length descending, so the
+    // first match is the longest operator possible.
+    operators: Vec<String>,
+
+    // The tokens of a psql meta-command which ended the previous statement (ex: `\gexec` in `SELECT 1 \gexec`), it is
+    // the next statement.
+    pending_meta_command: Option<Tokens<'s>>,
 }
 
 impl<'s> Iterator for Tokenizer<'s> {
     type Item = Statement<'s>;
 
     fn next(&mut self) -> Option<Self::Item> {
+        if let Some(tokens) = self.pending_meta_command.take() {
+            return Some(Statement { input: self.input, leading: Tokens::new(), tokens, diagnostics: Vec::new() });
+        }
         if self.next_offset >= self.input.len() {
             return None;
         }
@@ -47,7 +132,16 @@ impl<'s> Iterator for Tokenizer<'s> {
 
 impl<'s> Tokenizer<'s> {
     pub(crate) fn new(input: &'s str, options: Options) -> Self {
+        let mut operators: Vec<String> = OPERATORS
+            .iter()
+            .chain(dialect_operators(options.dialect))
+            .map(|op| op.to_string())
+            .chain(options.custom_operators.iter().filter(|op| is_valid_custom_operator(op)).cloned())
+            .collect();
+        operators.sort_by(|a, b| b.len().cmp(&a.len()).then(a.cmp(b)));
+        operators.dedup();
         Tokenizer {
+            operators,
             input,
             options,
             offset: 0,
@@ -55,6 +149,11 @@ impl<'s> Tokenizer<'s> {
             line: 1,
             column: 0,
             token_start: { Position { line: 1, column: 1, offset: 0 } },
+            executable_comment_depth: 0,
+            closing_chars: Vec::new(),
+            flat_closing_chars: Vec::new(),
+            diagnostics: Vec::new(),
+            pending_meta_command: None,
         }
     }
 
@@ -62,9 +161,9 @@ impl<'s> Tokenizer<'s> {
     #[inline]
     fn get_next_char(&mut self, input_iter: &mut std::str::Chars) -> Option<char> {
         let next_char = input_iter.next();
-        if next_char.is_some() {
+        if let Some(c) = next_char {
             self.offset = self.next_offset;
-            self.next_offset += next_char.as_ref().unwrap().len_utf8();
+            self.next_offset += c.len_utf8();
             self.column += 1;
         }
         next_char
@@ -99,7 +198,7 @@ impl<'s> Tokenizer<'s> {
             // New Line.
             //
             self.line += 1;
-            self.column = 1;
+            self.column = 0;
         } else if c == '\r' {
             //
             // Carriage Return (ignored).
@@ -187,7 +286,9 @@ impl<'s> Tokenizer<'s> {
                 // We found the end of the comment.
                 self.capture_token(tokens, self.offset, self.next_offset, TokenValue::Comment);
                 self.line += 1;
-                self.column = 1;
+                self.column = 0;
+                self.token_start.line = self.line;
+                self.token_start.column = 1;
                 return;
             }
         }
@@ -230,7 +331,194 @@ impl<'s> Tokenizer<'s> {
             }
             next_char = self.get_next_char(input_iter);
         }
-        self.capture_token(tokens, self.next_offset, self.next_offset, TokenValue::Comment);
+        let comment = &self.input[self.token_start.offset..self.next_offset];
+        let value_constructor = if comment.starts_with("/*+") {
+            TokenValue::OptimizerHint
+        } else if comment.starts_with("/*!") || comment.starts_with("/*M!") {
+            TokenValue::ExecutableComment
+        } else {
+            TokenValue::Comment
+        };
+        self.capture_token(tokens, self.next_offset, self.next_offset, value_constructor);
+    }
+
+    // Capture the opening of a MySQL executable comment (`/*!50001`, including the version) and a fragment with the
+    // tokens of its content.
+    //
+    // Returns the character where the capture of the content stopped: the `*` of the closing `*/`, or any character
+    // that ended the fragment first (closing parenthesis, statement delimiter or the end of the input).
+    fn capture_executable_comment(
+        &mut self,
+        input_iter: &mut std::str::Chars,
+        delimiter: &str,
+        tokens: &mut Tokens<'s>,
+    ) -> Option<char> {
+        // Skip the `/*!` or `/*M!` and the version number.
+        self.forward_iter(input_iter, if self.check_delimiter("/*M!") { 3 } else { 2 });
+        let version_len = self.input[self.next_offset..].chars().take_while(|c| c.is_ascii_digit()).count();
+        self.forward_iter(input_iter, version_len);
+        self.capture_token(tokens, self.next_offset, self.next_offset, TokenValue::ExecutableComment);
+
+        // The content of the comment is captured like the content of parentheses, `capture_fragment` stops at the
+        // closing `*/` when `executable_comment_depth` is not 0.
+        let mut nested_tokens = Tokens::new();
+        self.executable_comment_depth += 1;
+        let next_char = self.capture_fragment(input_iter, delimiter, &mut nested_tokens);
+        self.executable_comment_depth -= 1;
+        self.add_token(
+            TokenValue::Fragment(nested_tokens, FragmentKind::ExecutableComment),
+            self.offset,
+            self.offset,
+            tokens,
+        );
+        next_char
+    }
+
+    // Returns the length in bytes of the psql variable starting at the current position (including the colon), or
+    // `None` if the colon is not the start of a variable.
+    //
+    // See: https://www.postgresql.org/docs/current/app-psql.html#APP-PSQL-INTERPOLATION
+    fn psql_variable_len(&self) -> Option<usize> {
+        let input = &self.remaining_input()[1..];
+        let name_len = |name: &str| name.find(|c: char| !c.is_alphanumeric() && c != '_').unwrap_or(name.len());
+        let len = match input.chars().next()? {
+            // `:'name'` (interpolated as a string constant) or `:"name"` (interpolated as an identifier).
+            quote @ ('\'' | '"') => {
+                let len = name_len(&input[1..]);
+                (len > 0 && input[1 + len..].starts_with(quote)).then_some(len + 2)?
+            }
+            // `:{?name}` (whether the variable is defined).
+            '{' if input.starts_with("{?") => {
+                let len = name_len(&input[2..]);
+                (len > 0 && input[2 + len..].starts_with('}')).then_some(len + 3)?
+            }
+            ':' => return None,
+            _ => Some(name_len(input)).filter(|&len| len > 0)?,
+        };
+        Some(len + 1)
+    }
+
+    // Capture a psql meta-command (`\connect db`), from the backslash (already consumed) to the end of the line.
+    //
+    // The trailing whitespace and the end of the line are not part of the token.
+    fn capture_meta_command(&mut self, input_iter: &mut std::str::Chars, tokens: &mut Tokens<'s>) {
+        let line = self.remaining_input().split('\n').next().unwrap_or_default().trim_end();
+        self.forward_iter(input_iter, line.chars().count() - 1);
+        self.capture_token(tokens, self.next_offset, self.next_offset, TokenValue::MetaCommand);
+    }
+
+    // Capture the comments following the statement delimiter on the same line (ex: `SELECT 1; -- one`), they are
+    // trailing comments of the statement rather than leading comments of the next one.
+    //
+    // Nothing is captured unless the rest of the line is only made of comments. The end of the line is not consumed
+    // unless it ends a `--` or `#` comment.
+    fn capture_trailing_comments(&mut self, input_iter: &mut std::str::Chars, tokens: &mut Tokens<'s>) {
+        if !self.is_followed_by_comments() {
+            return;
+        }
+        while let Some(c) = self.input[self.next_offset..].chars().next().filter(|&c| c != '\n') {
+            self.get_next_char(input_iter);
+            if c == '/' {
+                self.capture_multi_line_comment(input_iter, tokens);
+            } else if c == '-' || c == '#' {
+                self.capture_single_line_comment(input_iter, tokens);
+                return;
+            } else {
+                self.process_newline(c);
+                self.capture_token(tokens, self.offset, self.next_offset, TokenValue::Any);
+            }
+        }
+    }
+
+    // Returns whether the rest of the current line (after the current character) is only made of comments (at least
+    // one), see `capture_trailing_comments`.
+    //
+    // Optimizer hints and executable comments are not comments, nested comments are not recognized.
+    fn is_followed_by_comments(&self) -> bool {
+        let mut rest = self.input[self.next_offset..].split('\n').next().unwrap_or_default();
+        let mut found = false;
+        loop {
+            rest = rest.trim_start_matches([' ', '\t', '\r']);
+            if rest.is_empty() {
+                return found;
+            } else if rest.starts_with("--")
+                || (rest.starts_with('#') && !self.operators.iter().any(|op| rest.starts_with(op.as_str())))
+            {
+                return true;
+            } else if rest.starts_with("/*") && !["/*+", "/*!", "/*M!"].iter().any(|prefix| rest.starts_with(prefix)) {
+                let Some(len) = rest[2..].find("*/") else {
+                    return false;
+                };
+                rest = &rest[len + 4..];
+                found = true;
+            } else {
+                return false;
+            }
+        }
+    }
+
+    // Capture the inline data of a `COPY ... FROM STDIN` statement, from the line following the statement to the `\.`
+    // line (included).
+    //
+    // The rest of the line of the statement is tokenized as usual (ex: a trailing comment), the data always starts on a
+    // new line.
+    fn capture_copy_data(&mut self, input_iter: &mut std::str::Chars, delimiter: &str, tokens: &mut Tokens<'s>) {
+        // The end of the line may already be consumed by a trailing comment.
+        if !self.input[..self.next_offset].ends_with('\n') {
+            let Some(line_len) = self.input[self.next_offset..].find('\n') else {
+                return;
+            };
+            // The tokens of the rest of the line are captured from an iterator limited to the line, the characters
+            // read from it are then skipped in the input iterator.
+            let start = self.next_offset;
+            let mut line_iter = self.input[start..start + line_len].chars();
+            while self.capture_fragment(&mut line_iter, delimiter, tokens).is_some() {
+                // A closing character, a delimiter or a backslash is an ordinary token in the rest of the line.
+                self.capture_token(tokens, self.next_offset, self.next_offset, TokenValue::Any);
+            }
+            let count = self.input[start..self.next_offset].chars().count();
+            if count > 0 {
+                input_iter.nth(count - 1);
+            }
+            let c = self.get_next_char(input_iter).expect("the end of the line is in the input");
+            self.process_newline(c);
+        }
+        self.token_start = Position { line: self.line, column: 1, offset: self.next_offset };
+
+        // Look for the end-of-data marker.
+        let data = &self.input[self.next_offset..];
+        let mut len = data.len();
+        let mut line_start = 0;
+        for line in data.split_inclusive('\n') {
+            if line.trim_end_matches(['\n', '\r']) == "\\." {
+                len = line_start + 2;
+                break;
+            }
+            line_start += line.len();
+        }
+        for _ in 0..data[..len].chars().count() {
+            let c = self.get_next_char(input_iter).expect("the data is in the input");
+            self.process_newline(c);
+        }
+        self.capture_token(tokens, self.next_offset, self.next_offset, TokenValue::CopyData);
+    }
+
+    // Returns the closing delimiter of an Oracle alternative quoting literal (ex: `]'` for `q'[...]'`) when positioned
+    // on its opening quote, or `None` if the quote is not followed by a valid delimiter.
+    //
+    // The delimiter can be any character except a whitespace, `[`, `{`, `<` and `(` are closed by their matching
+    // bracket.
+    // See: https://docs.oracle.com/en/database/oracle/oracle-database/23/sqlrf/Literals.html#GUID-1824CBAA-6E16-4921-B2A6-112FB02248DA
+    fn q_quote_closing_delimiter(&self) -> Option<String> {
+        let opening = self.remaining_input()[1..].chars().next().filter(|c| !c.is_whitespace())?;
+        let closing = match opening {
+            '[' => ']',
+            '{' => '}',
+            '<' => '>',
+            '(' => ')',
+            c => c,
+        };
+        Some(format!("{}'", closing))
     }
 
     // Capture a quoted identifier or a string literal.
@@ -240,6 +528,8 @@ impl<'s> Tokenizer<'s> {
     // - String literals can be delimited by single quotes (ex: 'O''Reilly') or double quotes (ex: "O'Reilly").
     // - The quotes can be escaped by repeating the quote character, e.g., to create an identifier named
     //   'IDENTIFIER "X"', use 'IDENTIFIER ""X""'.
+    // - When `backslash_escapes` is true, any character can also be escaped by a backslash (ex: 'It\'s' in MySQL or
+    //   E'It\'s' in PostgreSQL).
     //
     // Because this function has to peek the next character to check for an escaped delimiter, it returns the next
     // character to be processed by the tokenizer.
@@ -247,11 +537,18 @@ impl<'s> Tokenizer<'s> {
         &mut self,
         input_iter: &mut std::str::Chars,
         quote_char: char,
+        backslash_escapes: bool,
         tokens: &mut Tokens<'s>,
     ) -> Option<char> {
         let mut next_char = self.get_next_char(input_iter);
         while let Some(c) = next_char {
-            if c == quote_char {
+            if c == '\\' && backslash_escapes {
+                // The escaped character is part of the token (and may be a new line).
+                next_char = self.get_next_char(input_iter);
+                if let Some(escaped) = next_char {
+                    self.process_newline(escaped);
+                }
+            } else if c == quote_char {
                 // Quote found, we need to check if it's an escaped quote (repeated quote).
                 next_char = self.get_next_char(input_iter);
                 if next_char.as_ref() != Some(&quote_char) {
@@ -311,12 +608,20 @@ impl<'s> Tokenizer<'s> {
                 // `next_char`).
                 self.capture_token(tokens, self.offset, self.offset, TokenValue::Any);
                 return next_char;
+            } else if c == '\\' && self.options.psql {
+                //
+                // psql meta-command (`\connect db`).
+                //
+                // Like the delimiter, a meta-command ends the statement: the last token before the backslash is
+                // captured and the backslash is returned to the caller.
+                self.capture_token(tokens, self.offset, self.offset, TokenValue::Any);
+                return next_char;
             } else if c.is_whitespace() {
                 //
                 // Whitespace (could be \s, \t, \r, \n, etc.).
                 //
                 self.capture_token(tokens, self.offset, self.next_offset, TokenValue::Any);
-            } else if c == '#' || (c == '-' && self.check_delimiter("--")) {
+            } else if (c == '#' && self.match_operator().is_none()) || (c == '-' && self.check_delimiter("--")) {
                 //
                 // Single-line comment starting by '#' (MySQL).
                 // Single-line comment starting by '--' (most SQL dialects).
@@ -328,11 +633,40 @@ impl<'s> Tokenizer<'s> {
                 // Either a multi-line comment '/* ... */' or a division operator.
                 //
                 self.capture_token(tokens, self.offset, self.offset, TokenValue::Any);
-                self.capture_multi_line_comment(input_iter, tokens);
+                if self.options.tokenize_executable_comments
+                    && (self.check_delimiter("/*!") || self.check_delimiter("/*M!"))
+                    && self.check_depth()
+                {
+                    //
+                    // MySQL executable comment tokenized as SQL (`/*!50001`, Fragment, `*/`).
+                    //
+                    next_char = self.capture_executable_comment(input_iter, delimiter, tokens);
+                    if next_char == Some('*') && self.check_delimiter("*/") {
+                        // Capturing the end of the comment.
+                        self.forward_iter(input_iter, 1);
+                        self.capture_token(tokens, self.next_offset, self.next_offset, TokenValue::ExecutableComment);
+                    } else {
+                        // End of the input, statement delimiter or closing parenthesis found.
+                        return next_char;
+                    }
+                } else {
+                    self.capture_multi_line_comment(input_iter, tokens);
+                }
+            } else if c == '*' && self.executable_comment_depth > 0 && self.check_delimiter("*/") {
+                //
+                // End of an executable comment.
+                //
+                // Capture the last token before the end of the comment and return to the caller.
+                self.capture_token(tokens, self.offset, self.offset, TokenValue::Any);
+                return next_char;
             } else if c == '\'' || c == '"' || c == '`' {
                 //
                 // Quoted identifier or constant.
                 //
+                // MySQL string constants honour backslash escapes unless the `NO_BACKSLASH_ESCAPES` SQL mode is
+                // enabled.
+                let mut backslash_escapes =
+                    c != '`' && self.options.dialect == Dialect::MySql && !self.options.no_backslash_escapes;
                 if c == '\'' && self.offset > self.token_start.offset {
                     // There is an introducer:
                     // - Escaped string constant (E'hello\\tworld').
@@ -340,6 +674,21 @@ impl<'s> Tokenizer<'s> {
                     // - Bit-String constant (B'1001', X'1FF').
                     // - String constant with a character set introducer (_latin1'hello').
                     let introducer = &self.input[self.token_start.offset..self.offset];
+                    if self.options.dialect == Dialect::Oracle
+                        && (introducer.eq_ignore_ascii_case("q") || introducer.eq_ignore_ascii_case("nq"))
+                    {
+                        if let Some(closing) = self.q_quote_closing_delimiter() {
+                            // Alternative quoting (Oracle: q'[It's fine]', nq'!...!').
+                            self.forward_iter(input_iter, 1);
+                            next_char = self.capture_delimited_token(
+                                input_iter,
+                                &closing,
+                                tokens,
+                                TokenValue::QuotedIdentifierOrConstant,
+                            );
+                            continue;
+                        }
+                    }
                     let first_char = introducer.chars().next().unwrap();
                     if first_char == 'B' || first_char == 'b' || first_char == 'X' || first_char == 'x' {
                         // Escaped quotes are not allowed by Bit-String constants.
@@ -351,8 +700,10 @@ impl<'s> Tokenizer<'s> {
                         );
                         continue;
                     }
+                    // Escaped string constants (PostgreSQL) always honour backslash escapes.
+                    backslash_escapes |= introducer == "E" || introducer == "e";
                 }
-                next_char = self.capture_quoted_identifier_or_constant(input_iter, c, tokens);
+                next_char = self.capture_quoted_identifier_or_constant(input_iter, c, backslash_escapes, tokens);
                 continue;
             } else if (c == 'U' || c == 'u') && self.remaining_input().starts_with("U&\"") {
                 //
@@ -361,7 +712,7 @@ impl<'s> Tokenizer<'s> {
                 // A Unicode escape string constant starts with U& (upper or lower case letter U followed by ampersand)
                 // immediately before the opening quote, without any spaces in between, for example U&"foo".
                 self.forward_iter(input_iter, 2);
-                next_char = self.capture_quoted_identifier_or_constant(input_iter, '"', tokens);
+                next_char = self.capture_quoted_identifier_or_constant(input_iter, '"', false, tokens);
                 continue;
             } else if c == '$' {
                 //
@@ -390,66 +741,136 @@ impl<'s> Tokenizer<'s> {
                         TokenValue::QuotedIdentifierOrConstant,
                     );
                 } else {
-                    // We've found a parameter marker (`$1`, `$id`)
+                    // We've found a parameter marker (`$1`, `$id`) or a variable (Snowflake or DuckDB `$name`).
+                    let dialect = self.options.dialect;
                     self.capture_token(
                         tokens,
                         if next_char.is_some() { self.offset } else { self.next_offset },
                         self.next_offset,
-                        TokenValue::ParameterMarker,
+                        |value| parameter_marker_or_variable(dialect, value),
                     );
                 }
                 continue;
+            } else if c == '?' && self.question_mark_operator_len().is_some() {
+                //
+                // Operator starting with a question mark (ex: `?|`), see `QuestionMarkMode`.
+                //
+                let len = self.question_mark_operator_len().unwrap_or_default();
+                self.capture_operator(input_iter, len, tokens);
+            } else if matches!(c, ':' | '?' | '@') && self.match_operator().is_some() {
+                //
+                // Operator starting like a parameter marker (ex: `@>`, `?|`, `:=`, `::`).
+                //
+                self.try_capture_operator(input_iter, tokens);
+            } else if c == ':' && self.options.psql && self.psql_variable_len().is_some() {
+                //
+                // psql variable (`:name`, `:'name'`, `:"name"`, `:{?name}`).
+                //
+                let len = self.psql_variable_len().unwrap_or_default();
+                self.capture_token(tokens, self.offset, self.offset, TokenValue::Any);
+                self.forward_iter(input_iter, self.input[self.offset..self.offset + len].chars().count() - 1);
+                self.capture_token(tokens, self.next_offset, self.next_offset, |value| {
+                    TokenValue::Variable(value, VariableScope::Client)
+                });
+            } else if c == ':'
+                && self.closing_chars.last() == Some(&']')
+                && self.token_start.offset == self.offset
+                && tokens.last().is_some_and(is_operand)
+            {
+                //
+                // Slice operator (ex: `arr[1:3]`, `arr[lower:upper]`), a colon following an operand in brackets is not
+                // a parameter marker (unlike `ARRAY[:a, :b]`).
+                //
+                self.capture_operator(input_iter, 1, tokens);
             } else if c == ':' || c == '?' || c == '@' {
                 //
                 // A Parameter Marker
                 //
                 next_char = self.get_next_char(input_iter);
+                let is_system_variable = c == '@' && next_char.as_ref() == Some(&'@');
+                if is_system_variable {
+                    // A system variable (`@@version`, `@@session.sql_mode`) is captured as a single token.
+                    next_char = self.get_next_char(input_iter);
+                }
                 while next_char.is_some()
                     && (next_char.as_ref().unwrap().is_ascii_alphanumeric() || next_char.as_ref() == Some(&'_'))
                 {
                     next_char = self.get_next_char(input_iter);
                 }
+                if is_system_variable
+                    && next_char.as_ref() == Some(&'.')
+                    && system_variable_scope(&self.input[self.token_start.offset..self.offset]).is_some()
+                {
+                    // The name of a scoped system variable (ex: `sql_mode` in `@@session.sql_mode`).
+                    next_char = self.get_next_char(input_iter);
+                    while next_char.is_some()
+                        && (next_char.as_ref().unwrap().is_ascii_alphanumeric() || next_char.as_ref() == Some(&'_'))
+                    {
+                        next_char = self.get_next_char(input_iter);
+                    }
+                }
                 if c == ':' && next_char.as_ref() == Some(&':') && self.token_start.offset + 1 == self.offset {
                     // Special case for the PostgreSQL type casting operator `::` (consuming next_char).
                     self.capture_token(tokens, self.next_offset, self.next_offset, TokenValue::Operator);
                 } else {
-                    // We've found a parameter marker (`$1`, `$id`)
-                    self.capture_token(
-                        tokens,
-                        if next_char.is_some() { self.offset } else { self.next_offset },
-                        self.next_offset,
-                        TokenValue::ParameterMarker,
-                    );
+                    // We've found a parameter marker (`$1`, `$id`) or a variable (`@@version`), the next character is
+                    // the start of the next token.
+                    let end_offset = if next_char.is_some() { self.offset } else { self.next_offset };
+                    let dialect = self.options.dialect;
+                    self.capture_token(tokens, end_offset, end_offset, |value| {
+                        parameter_marker_or_variable(dialect, value)
+                    });
                     continue;
                 }
-            } else if c == '(' {
+            } else if let Some(kind) = bracket_kind(c).filter(|_| !self.check_depth()) {
                 //
-                // Start of a parentheses block.
+                // Start of a block nested deeper than `Options::max_depth`, tokenized flat.
+                //
+                self.capture_token(tokens, self.offset, self.offset, TokenValue::Any);
+                self.capture_token(tokens, self.next_offset, self.next_offset, TokenValue::Any);
+                self.flat_closing_chars.extend(kind.closing_char());
+            } else if matches!(c, ')' | ']' | '}') && self.flat_closing_chars.last() == Some(&c) {
+                //
+                // End of a block tokenized flat.
+                //
+                self.flat_closing_chars.pop();
+                self.capture_token(tokens, self.offset, self.offset, TokenValue::Any);
+                self.capture_token(tokens, self.next_offset, self.next_offset, TokenValue::Any);
+            } else if let Some(kind) = bracket_kind(c) {
+                //
+                // Start of a parentheses, brackets or braces block.
                 //
                 // Capture the previous token if any.
                 self.capture_token(tokens, self.offset, self.offset, TokenValue::Any);
-                // Capture the parentheses as a token.
+                // Capture the opening character as a token.
                 self.capture_token(tokens, self.next_offset, self.next_offset, TokenValue::Any);
+                let closing_char = kind.closing_char();
                 let mut nested_tokens = Tokens::new();
+                self.closing_chars.extend(closing_char);
                 next_char = self.capture_fragment(input_iter, delimiter, &mut nested_tokens);
-                self.add_token(TokenValue::Fragment(nested_tokens), self.offset, self.offset, tokens);
-                // We cannot assume the next character is the end of the parentheses block because we could have
-                // reached the end of the input or the statement delimiter.
-                if next_char.as_ref() == Some(&')') {
-                    // Capturing the end parenthesis.
+                self.closing_chars.pop();
+                self.add_token(TokenValue::Fragment(nested_tokens, kind), self.offset, self.offset, tokens);
+                // We cannot assume the next character is the end of the block because we could have reached the end of
+                // the input, the statement delimiter or the end of an enclosing block (ex: `(a[1)`).
+                if next_char.is_some() && next_char == closing_char {
+                    // Capturing the closing character.
                     self.capture_token(tokens, self.next_offset, self.next_offset, TokenValue::Any);
                 } else {
-                    // End of the input or statement delimiter found.
+                    // End of the input, statement delimiter or end of an enclosing block found.
                     return next_char;
                 }
-            } else if c == ')' {
+            } else if matches!(c, ')' | ']' | '}') && (self.closing_chars.is_empty() || self.closing_chars.contains(&c))
+            {
+                //
+                // End of a parentheses, brackets or braces block.
                 //
-                // End of a parentheses block.
+                // A closing character that doesn't match any open block (ex: the `]` of `(a])`) is an ordinary token,
+                // except at the top level of the statement where it is handled by `get_next_statement`.
                 //
-                // Capture the last token before the end parenthesis.
+                // Capture the last token before the closing character.
                 self.capture_token(tokens, self.offset, self.offset, TokenValue::Any);
-                // Then we return to the caller so it can capture the end parenthesis as a token in the same fragment
-                // level as the opening parenthesis.
+                // Then we return to the caller so it can capture the closing character as a token in the same fragment
+                // level as the opening character.
                 return next_char;
             } else if c == '.' {
                 //
@@ -533,21 +954,80 @@ impl<'s> Tokenizer<'s> {
     //
     // Returns true if an operator was found, false otherwise.
     fn try_capture_operator(&mut self, input_iter: &mut std::str::Chars, tokens: &mut Tokens<'s>) -> bool {
-        let remaining_input = &self.input[self.offset..];
-        let operator = OPERATORS.iter().find(|&op| remaining_input.starts_with(op));
-        if let Some(op) = operator {
-            // We found an operator, we need to capture the current token before the operator.
-            self.capture_token(tokens, self.offset, self.offset, TokenValue::Any);
-            // Capture the operator
-            self.capture_token(tokens, self.offset + op.len(), self.offset + op.len(), TokenValue::Operator);
-            // We need to move the iterator to the end of the operator.
-            self.forward_iter(input_iter, op.chars().count() - 1);
+        if let Some(len) = self.match_operator() {
+            self.capture_operator(input_iter, len, tokens);
             true
         } else {
             false
         }
     }
 
+    // Returns whether a block (parentheses, brackets, braces or executable comment) starting at the current position
+    // can be captured as a nested fragment without exceeding `Options::max_depth`.
+    //
+    // Otherwise the block is tokenized flat and an `Error::MaxDepthExceeded` diagnostic is recorded for the first
+    // block too deep of the statement.
+    fn check_depth(&mut self) -> bool {
+        if self.closing_chars.len() + self.executable_comment_depth < self.options.max_depth {
+            return true;
+        }
+        if !self.diagnostics.iter().any(|error| matches!(error, Error::MaxDepthExceeded { .. })) {
+            let position = Position::new(self.line, self.column, self.offset);
+            self.diagnostics.push(Error::MaxDepthExceeded { max_depth: self.options.max_depth, position });
+        }
+        false
+    }
+
+    // Capture an operator of the given length (in bytes) starting at the current position.
+    fn capture_operator(&mut self, input_iter: &mut std::str::Chars, len: usize, tokens: &mut Tokens<'s>) {
+        // We found an operator, we need to capture the current token before the operator.
+        self.capture_token(tokens, self.offset, self.offset, TokenValue::Any);
+        // Capture the operator
+        self.capture_token(tokens, self.offset + len, self.offset + len, TokenValue::Operator);
+        // We need to move the iterator to the end of the operator.
+        let count = self.input[self.offset..self.offset + len].chars().count();
+        self.forward_iter(input_iter, count - 1);
+    }
+
+    // Returns the length in bytes of the operator starting with the question mark at the current position, or `None`
+    // if the question mark is a parameter marker.
+    //
+    // - `QuestionMarkMode::Parameter`: `?` is always a parameter marker.
+    // - `QuestionMarkMode::Operator`: `?`, `?|`, `?&`... are operators.
+    // - `QuestionMarkMode::Jdbc`: `?` is a parameter marker, the operators are escaped by doubling the question mark
+    //   (`??`, `??|`, `??&`...).
+    fn question_mark_operator_len(&self) -> Option<usize> {
+        let mode = self.options.question_mark.unwrap_or(match self.options.dialect {
+            Dialect::PostgreSql => QuestionMarkMode::Operator,
+            _ => QuestionMarkMode::Parameter,
+        });
+        let input = match mode {
+            QuestionMarkMode::Parameter => return None,
+            QuestionMarkMode::Operator => self.remaining_input(),
+            QuestionMarkMode::Jdbc => {
+                self.remaining_input().strip_prefix('?').filter(|input| input.starts_with('?'))?
+            }
+        };
+        let len = QUESTION_MARK_OPERATORS.iter().find(|op| input.starts_with(*op))?.len();
+        Some(if mode == QuestionMarkMode::Jdbc { len + 1 } else { len })
+    }
+
+    // Returns the length in bytes of the longest operator starting at the current position.
+    //
+    // Like in PostgreSQL, an operator cannot run into a comment: `||/*` is the `||` operator followed by a comment.
+    fn match_operator(&self) -> Option<usize> {
+        let remaining_input = self.remaining_input();
+        self.operators
+            .iter()
+            .find(|op| {
+                remaining_input.starts_with(op.as_str()) && {
+                    let next = &remaining_input[op.len()..];
+                    !(op.ends_with('/') && next.starts_with('*') || op.ends_with('-') && next.starts_with('-'))
+                }
+            })
+            .map(|op| op.len())
+    }
+
     // Capture a token delimited by the given delimiter.
     //
     // The delimiter can be a single character or a multi-character delimiter.
@@ -608,20 +1088,39 @@ impl<'s> Tokenizer<'s> {
 
         // Under normal circumstances, the tokenizer will either return None if the input is empty or the first
         // character if the delimiter if found.
-        // Nevertheless we need to handle the case where the tokenizer was stopped by a closing parenthesis without a
-        // matching opening parenthesis. This is why we need to loop until we find the delimiter or reach the end of the
-        // input.
+        // Nevertheless we need to handle the case where the tokenizer was stopped by a closing character (`)`, `]` or
+        // `}`) without a matching opening character. This is why we need to loop until we find the delimiter or reach
+        // the end of the input.
         while self.capture_fragment(input_iter, delimiter, &mut tokens).is_some() {
             if self.check_delimiter(delimiter) {
                 // The delimiter was found but not captured as a token, we need to capture it now.
                 // Moving forward the iterator until the end of the delimiter.
                 self.forward_iter(input_iter, delimiter.chars().count() - 1);
                 self.capture_token(&mut tokens, self.next_offset, self.next_offset, TokenValue::StatementDelimiter);
+                self.capture_trailing_comments(input_iter, &mut tokens);
+                if is_copy_from_stdin(&tokens) {
+                    self.capture_copy_data(input_iter, delimiter, &mut tokens);
+                }
+                break;
+            } else if self.options.psql && self.check_delimiter("\\") {
+                // A meta-command is a statement of its own.
+                let mut meta_command = Tokens::new();
+                self.capture_meta_command(input_iter, &mut meta_command);
+                if is_copy_from_stdin(&meta_command) {
+                    self.capture_copy_data(input_iter, delimiter, &mut meta_command);
+                }
+                if tokens.iter().all(|token| token.is_comment()) {
+                    tokens.append(&mut meta_command);
+                } else {
+                    // The meta-command ends the current statement (ex: `SELECT 1 \gexec`), it is returned as the next
+                    // statement.
+                    self.pending_meta_command = Some(meta_command);
+                }
                 break;
             } else {
-                // We need to continue the tokenization because we found a closing parenthesis without a matching
-                // opening parenthesis.
-                // We need to capture the closing parenthesis as a token before resuming the tokenization.
+                // We need to continue the tokenization because we found a closing character without a matching
+                // opening character.
+                // We need to capture the closing character as a token before resuming the tokenization.
                 self.capture_token(&mut tokens, self.next_offset, self.next_offset, TokenValue::Any);
             }
         }
@@ -629,7 +1128,17 @@ impl<'s> Tokenizer<'s> {
         match tokens.is_empty() {
             // We reached the end of the input without finding any token.
             true => None,
-            false => Some(Statement { input: self.input, tokens }),
+            false => {
+                self.flat_closing_chars.clear();
+                let diagnostics = std::mem::take(&mut self.diagnostics);
+                // The comments preceding the code of the statement are detached from its tokens (a statement made of
+                // comments only keeps them as tokens).
+                let mut leading = Tokens::new();
+                if let Some(first) = tokens.iter().position(|t| !t.is_comment() && !t.is_statement_delimiter()) {
+                    leading.extend(tokens.drain(..first));
+                }
+                Some(Statement { input: self.input, leading, tokens, diagnostics })
+            }
         }
     }
 
@@ -702,6 +1211,45 @@ impl<'s> Tokenizer<'s> {
     }
 }
 
+// Returns whether a token ends an operand (a constant, an identifier, a parameter, `)` or `]`).
+fn is_operand(token: &Token) -> bool {
+    match token.value {
+        TokenValue::NumericConstant(_)
+        | TokenValue::IdentifierOrKeyword(_)
+        | TokenValue::QuotedIdentifierOrConstant(_)
+        | TokenValue::ParameterMarker(_)
+        | TokenValue::Variable(..) => true,
+        TokenValue::Any(value) => value == ")" || value == "]",
+        _ => false,
+    }
+}
+
+// Returns whether a custom operator can be matched by the tokenizer, see `Options::custom_operators`.
+//
+// Identifiers, keywords, numeric constants and whitespace are captured before looking for an operator, so an operator
+// starting with an alphanumeric character, an underscore or a whitespace would never be found.
+fn is_valid_custom_operator(operator: &str) -> bool {
+    operator.chars().next().is_some_and(|c| !c.is_alphanumeric() && c != '_' && !c.is_whitespace())
+}
+
+// Returns whether the tokens are a `COPY ... FROM STDIN` statement or a psql `\copy ... from stdin` meta-command,
+// which are followed by inline data.
+fn is_copy_from_stdin(tokens: &[Token]) -> bool {
+    let mut code = tokens.iter().filter(|token| !token.is_comment());
+    match code.next().map(|token| &token.value) {
+        Some(TokenValue::IdentifierOrKeyword(keyword)) if keyword.eq_ignore_ascii_case("COPY") => {
+            let code: Vec<_> = code.collect();
+            code.windows(2).any(|pair| pair[0].is_keyword("FROM") && pair[1].is_keyword("STDIN"))
+        }
+        Some(TokenValue::MetaCommand(command)) => {
+            let words: Vec<_> = command.split_whitespace().map(|word| word.to_ascii_lowercase()).collect();
+            words.first().is_some_and(|word| word == "\\copy")
+                && words.windows(2).any(|pair| pair[0] == "from" && pair[1] == "stdin")
+        }
+        _ => false,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -767,6 +1315,88 @@ mod tests {
         assert_token!("n'こんにちは'", QuotedIdentifierOrConstant);
     }
 
+    #[test]
+    fn test_backslash_escapes() {
+        let tokenize = |input, options: Options| -> Vec<Vec<String>> {
+            Tokenizer::new(input, options)
+                .map(|s| s.tokens.as_str_array().iter().map(|t| t.to_string()).collect())
+                .collect()
+        };
+        let mysql = Options { dialect: Dialect::MySql, ..Default::default() };
+        let input = "INSERT INTO t VALUES ('It\\'s', \"a\\\"b\", 'c:\\\\', 'd''e');\nSELECT `a\\`;";
+        assert_eq!(
+            tokenize(input, mysql.clone()),
+            [
+                vec![
+                    "INSERT",
+                    "INTO",
+                    "t",
+                    "VALUES",
+                    "(",
+                    "'It\\'s'",
+                    ",",
+                    "\"a\\\"b\"",
+                    ",",
+                    "'c:\\\\'",
+                    ",",
+                    "'d''e'",
+                    ")",
+                    ";"
+                ],
+                vec!["SELECT", "`a\\`", ";"],
+            ]
+        );
+        // Without backslash escapes, the first string ends early.
+        let options = Options { no_backslash_escapes: true, ..mysql };
+        assert_eq!(tokenize("SELECT 'It\\'s';", options.clone())[0][..2], ["SELECT", "'It\\'"]);
+        assert_eq!(tokenize("SELECT 'c:\\';", Options::default())[0], ["SELECT", "'c:\\'", ";"]);
+        // Escaped string constants always honour backslash escapes.
+        assert_eq!(
+            tokenize("SELECT E'It\\'s\\\n', e'\\\\';", options)[0],
+            ["SELECT", "E'It\\'s\\\n'", ",", "e'\\\\'", ";"]
+        );
+        let statement = Tokenizer::new("SELECT E'\\\n' x", Options::default()).next().unwrap();
+        assert_eq!((statement.tokens[2].start.line, statement.tokens[2].start.column), (2, 3));
+    }
+
+    #[test]
+    fn test_oracle_alternative_quoting() {
+        let oracle = Options { dialect: Dialect::Oracle, ..Default::default() };
+        let tokenize = |input, options: Options| -> Vec<String> {
+            let statement = Tokenizer::new(input, options).next().unwrap();
+            statement.tokens.as_str_array().iter().map(|t| t.to_string()).collect()
+        };
+        assert_eq!(
+            tokenize(
+                "SELECT q'[It's [fine]]', Q'{a'}', q'<a>b>', q'(x)', q'!it's!', nq'#ü'#', NQ'|\n|' FROM dual",
+                oracle.clone()
+            ),
+            [
+                "SELECT",
+                "q'[It's [fine]]'",
+                ",",
+                "Q'{a'}'",
+                ",",
+                "q'<a>b>'",
+                ",",
+                "q'(x)'",
+                ",",
+                "q'!it's!'",
+                ",",
+                "nq'#ü'#'",
+                ",",
+                "NQ'|\n|'",
+                "FROM",
+                "dual"
+            ]
+        );
+        // Unterminated or without a delimiter.
+        assert_eq!(tokenize("SELECT q'[abc'", oracle.clone()), ["SELECT", "q'[abc'"]);
+        assert_eq!(tokenize("SELECT q' x'", oracle), ["SELECT", "q' x'"]);
+        // Other dialects don't support alternative quoting.
+        assert_eq!(tokenize("SELECT q'[It's]'", Options::default()), ["SELECT", "q'[It'", "s", "]", "'"]);
+    }
+
     #[test]
     fn test_bit_string_constant() {
         assert_token!("B'100'", QuotedIdentifierOrConstant);
@@ -867,6 +1497,44 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_variables() {
+        let tokenize = |input: &str, dialect: Dialect| -> Vec<String> {
+            let options = Options { dialect, ..Default::default() };
+            let statement = Tokenizer::new(input, options).next().unwrap();
+            statement.tokens.iter().skip(1).step_by(2).map(|t| format!("{:?}", t.value)).collect()
+        };
+        let sql = "SELECT @@version, @@SESSION.sql_mode, @@global.max_connections, @@local.x, @name, $name, $1";
+        assert_eq!(
+            tokenize(sql, Dialect::MySql),
+            [
+                "Variable(\"@@version\", System)",
+                "Variable(\"@@SESSION.sql_mode\", Session)",
+                "Variable(\"@@global.max_connections\", Global)",
+                "Variable(\"@@local.x\", Session)",
+                "Variable(\"@name\", User)",
+                "ParameterMarker(\"$name\")",
+                "ParameterMarker(\"$1\")",
+            ]
+        );
+        assert_eq!(
+            tokenize("SELECT @@ROWCOUNT, @local", Dialect::SqlServer),
+            ["Variable(\"@@ROWCOUNT\", System)", "Variable(\"@local\", Local)"]
+        );
+        assert_eq!(
+            tokenize("SELECT $name, $1, @name", Dialect::Snowflake),
+            ["Variable(\"$name\", Session)", "ParameterMarker(\"$1\")", "ParameterMarker(\"@name\")"]
+        );
+        assert_eq!(
+            tokenize("SELECT $name, $1", Dialect::DuckDb),
+            ["Variable(\"$name\", Session)", "ParameterMarker(\"$1\")"]
+        );
+        assert_eq!(
+            tokenize("SELECT @name, $name", Dialect::Generic),
+            ["ParameterMarker(\"@name\")", "ParameterMarker(\"$name\")"]
+        );
+    }
+
     #[test]
     fn test_operator_token() {
         assert_token!("!~*", Operator);
@@ -909,6 +1577,75 @@ mod tests {
         assert_tokens!("SELECT (1 + 2 + 3; SELECT 2", ["SELECT", "(", "1", "+", "2", "+", "3", ";"], ["SELECT", "2"]);
     }
 
+    #[test]
+    fn test_brackets_and_braces() {
+        // Returns the tokens of the first statement, fragments are written between `<kind:` and `>`.
+        fn describe(tokens: &[Token]) -> Vec<String> {
+            tokens
+                .iter()
+                .map(|t| match (t.fragment_kind(), t.children()) {
+                    (Some(kind), Some(children)) => format!("<{:?}:{}>", kind, describe(children).join(" ")),
+                    _ => t.to_string(),
+                })
+                .collect()
+        }
+        let tokenize = |input: &str| describe(&Tokenizer::new(input, Options::default()).next().unwrap().tokens);
+        assert_eq!(
+            tokenize("SELECT arr[1][2:3], {'a': [1, (2)]}, {fn now()}"),
+            [
+                "SELECT",
+                "arr",
+                "[",
+                "<Brackets:1>",
+                "]",
+                "[",
+                "<Brackets:2 : 3>",
+                "]",
+                ",",
+                "{",
+                "<Braces:'a' : [ <Brackets:1 , ( <Parentheses:2> )> ]>",
+                "}",
+                ",",
+                "{",
+                "<Braces:fn now ( <Parentheses:> )>",
+                "}"
+            ]
+        );
+        // A closing character closes the innermost block it matches, the blocks in between are left unclosed.
+        assert_eq!(tokenize("(a[1) b"), ["(", "<Parentheses:a [ <Brackets:1>>", ")", "b"]);
+        // A closing character without a matching opening character is an ordinary token.
+        assert_eq!(tokenize("(a]) }"), ["(", "<Parentheses:a ]>", ")", "}"]);
+        assert_eq!(tokenize("x[1; SELECT 1"), ["x", "[", "<Brackets:1>", ";"]);
+    }
+
+    #[test]
+    fn test_max_depth() {
+        // Returns the depth of the deepest fragment.
+        fn depth(tokens: &[Token]) -> usize {
+            tokens.iter().filter_map(|t| t.children()).map(|children| depth(children) + 1).max().unwrap_or(0)
+        }
+        let options = Options { max_depth: 2, ..Default::default() };
+        let statement = Tokenizer::new("SELECT ((([1]))), (1)", options).next().unwrap();
+        assert_eq!(depth(&statement.tokens), 2);
+        assert_eq!(statement.tokens.as_str_array().concat(), "SELECT((([1]))),(1)");
+        assert_eq!(statement.tokens[2].children().unwrap()[1].to_string(), "([1])");
+        assert!(matches!(
+            statement.diagnostics(),
+            [Error::MaxDepthExceeded { max_depth: 2, position }] if position.column == 10
+        ));
+
+        // Adversarial input must not exhaust the stack, even when walking the fragments.
+        let sql = format!("SELECT {}1{}; SELECT 2", "(".repeat(100_000), ")".repeat(100_000));
+        let statements: Vec<_> = Tokenizer::new(&sql, Options::default()).collect();
+        assert_eq!(statements.len(), 2);
+        assert_eq!(depth(&statements[0].tokens), 128);
+        assert_eq!(statements[0].diagnostics().len(), 1);
+        assert!(statements[1].diagnostics().is_empty());
+        assert_eq!(statements[0].sql(), &sql[..sql.len() - 9]);
+        assert!(statements[0].fingerprint().normalized.starts_with("select ((("));
+        assert!(crate::format(&sql, &crate::FormatOptions::default()).ends_with(");\nSELECT 2"));
+    }
+
     #[test]
     fn test_delimited_token() {
         assert_token!("$$O'Reilly$$", QuotedIdentifierOrConstant);
@@ -924,7 +1661,7 @@ mod tests {
         assert_token!("/** comment **/", Comment);
         assert_token!("/* comment */", Comment);
         assert_token!("/* /*nested*/comment */", Comment);
-        assert_token!("/*+ SET_VAR(foreign_key_checks=OFF) */", Comment);
+        assert_token!("/* + not a hint */", Comment);
         assert_tokens!("BEGIN /* not closed...", ["BEGIN", "/* not closed..."]);
         assert_tokens!("BEGIN /* not closed...; BEGIN", ["BEGIN", "/* not closed...; BEGIN"]);
         assert_tokens!("/* line 1 \r\n line 2 */", ["/* line 1 \r\n line 2 */"]);
@@ -936,6 +1673,246 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_token_positions_after_newlines() {
+        let statement = Tokenizer::new("-- a\n/* b\r\n c */ 'd\ne' f", Options::default()).next().unwrap();
+        let positions: Vec<_> = statement
+            .leading
+            .iter()
+            .chain(statement.tokens.iter())
+            .map(|t| ((t.start.line, t.start.column), (t.end.line, t.end.column)))
+            .collect();
+        assert_eq!(positions, [((1, 1), (1, 4)), ((2, 1), (3, 5)), ((3, 7), (4, 2)), ((4, 4), (4, 4))]);
+    }
+
+    #[test]
+    fn test_optimizer_hint_and_executable_comment_tokens() {
+        assert_token!("/*+ SET_VAR(foreign_key_checks=OFF) */", OptimizerHint);
+        assert_token!("/*!50001 CREATE ALGORITHM=UNDEFINED */", ExecutableComment);
+        assert_token!("/*M!100101 SET x=1 */", ExecutableComment);
+        assert_token!("/*! STRAIGHT_JOIN */", ExecutableComment);
+
+        let statement =
+            Tokenizer::new("SELECT /*+ INDEX(t idx) */ 1 /*!40101 , 2*/", Options::default()).next().unwrap();
+        assert_eq!(statement.tokens[1].hint(), Some("INDEX(t idx)"));
+        assert_eq!(statement.tokens[3].mysql_version(), Some(40101));
+        assert_eq!(statement.tokens[1].mysql_version(), None);
+        assert_eq!(statement.tokens[3].hint(), None);
+    }
+
+    #[test]
+    fn test_tokenize_executable_comments() {
+        let options = Options { tokenize_executable_comments: true, ..Default::default() };
+        let tokenize = |input| -> Vec<Vec<String>> {
+            Tokenizer::new(input, options.clone())
+                .map(|s| {
+                    s.leading
+                        .iter()
+                        .chain(s.tokens.iter())
+                        .map(|t| format!("{:?}", t.value).split('(').next().unwrap().to_string() + ":" + &t.to_string())
+                        .collect()
+                })
+                .collect()
+        };
+        assert_eq!(
+            tokenize("/*!50001 CREATE VIEW v AS SELECT (1) */; SELECT /*!*/ 1"),
+            [
+                vec![
+                    "ExecutableComment:/*!50001",
+                    "Fragment:CREATEVIEWvASSELECT(1)",
+                    "ExecutableComment:*/",
+                    "StatementDelimiter:;"
+                ],
+                vec![
+                    "IdentifierOrKeyword:SELECT",
+                    "ExecutableComment:/*!",
+                    "Fragment:",
+                    "ExecutableComment:*/",
+                    "NumericConstant:1"
+                ],
+            ]
+        );
+        let statement = Tokenizer::new("/*!40101 SET @x = 1 */", options.clone()).next().unwrap();
+        assert_eq!(statement.tokens[0].mysql_version(), Some(40101));
+        assert_eq!(statement.tokens[1].as_str_array(), ["SET", "@x", "=", "1"]);
+        assert_eq!((statement.tokens[2].start.column, statement.tokens[2].end.column), (21, 22));
+        // Unterminated executable comments.
+        assert_eq!(
+            tokenize("/*!50001 (SELECT 1; SELECT 2"),
+            [
+                vec!["ExecutableComment:/*!50001", "Fragment:(SELECT1", "StatementDelimiter:;"],
+                vec!["IdentifierOrKeyword:SELECT", "NumericConstant:2"]
+            ]
+        );
+        assert_eq!(
+            tokenize("SELECT /*!50001"),
+            [vec!["IdentifierOrKeyword:SELECT", "ExecutableComment:/*!50001", "Fragment:"]]
+        );
+    }
+
+    #[test]
+    fn test_copy_from_stdin() {
+        let input = "COPY t (a, b) FROM stdin; -- data\n1\tO'Reilly\n2\t(\n\\.\nSELECT 1;\n\
+                     copy t from STDIN;\r\n\\.\r\nCOPY t FROM stdin;\n3\t;";
+        let statements: Vec<_> = Tokenizer::new(input, Options::default()).collect();
+        assert_eq!(statements.len(), 4);
+        let data = statements[0].tokens.last().unwrap();
+        assert!(data.is_copy_data());
+        assert_eq!(data.value.as_ref(), "1\tO'Reilly\n2\t(\n\\.");
+        assert_eq!((data.start.line, data.start.column, data.end.line, data.end.column), (2, 1, 4, 2));
+        assert_eq!(statements[0].copy_data(), Some("1\tO'Reilly\n2\t(\n"));
+        assert_eq!(statements[1].tokens.as_str_array(), ["SELECT", "1", ";"]);
+        assert_eq!(statements[1].start().line, 5);
+        assert_eq!(statements[2].copy_data(), Some(""));
+        // Unterminated data ends with the input.
+        assert_eq!(statements[3].copy_data(), Some("3\t;"));
+
+        // The rest of the line of the statement is tokenized.
+        let input = "COPY t FROM stdin; /* a */ x ('b;\n1\n\\.\nSELECT 1";
+        let statements: Vec<_> = Tokenizer::new(input, Options::default()).collect();
+        assert_eq!(statements.len(), 2);
+        assert_eq!(
+            statements[0].tokens.as_str_array(),
+            ["COPY", "t", "FROM", "stdin", ";", "/* a */", "x", "(", "'b;", "1\n\\."]
+        );
+        assert_eq!(statements[0].copy_data(), Some("1\n"));
+        assert_eq!((statements[1].start().line, statements[1].start().column), (4, 1));
+
+        // psql `\copy` meta-command.
+        let options = Options { psql: true, ..Default::default() };
+        let statements: Vec<_> =
+            Tokenizer::new("\\copy t FROM stdin\n1\n\\.\n\\copy t TO stdout\nSELECT 1", options).collect();
+        assert_eq!(statements.len(), 3);
+        assert_eq!(statements[0].copy_data(), Some("1\n"));
+        assert_eq!(statements[1].copy_data(), None);
+        assert_eq!(statements[2].sql(), "SELECT 1");
+    }
+
+    #[test]
+    fn test_dialect_operators() {
+        let tokenize = |input: &str, options: Options| -> Vec<String> {
+            let statement = Tokenizer::new(input, options).next().unwrap();
+            statement.tokens.iter().map(|t| format!("{:?}", t.value)).collect()
+        };
+        let all = [
+            (Dialect::Generic, vec!["::", "!~*", "||", "<>", "<<"]),
+            (
+                Dialect::PostgreSql,
+                vec![
+                    "->", "->>", "#>", "#>>", "#-", "#", "@>", "<@", "&&", "-|-", "<->", "@@", "@?", "!!", "||/", "|/",
+                    ":=", "=>",
+                ],
+            ),
+            (Dialect::MySql, vec!["->", "->>", ":=", "<=>", "&&"]),
+            (Dialect::Sqlite, vec!["->", "->>"]),
+            (Dialect::DuckDb, vec!["->", "->>", "@>", "<@", "&&", "**", "//", ":=", "=>", "^@"]),
+            (Dialect::Oracle, vec!["=>", ":=", "**"]),
+            (Dialect::Snowflake, vec!["->", "->>", "=>"]),
+        ];
+        for (dialect, operators) in all {
+            for op in operators {
+                let options = Options { dialect, ..Default::default() };
+                let expected =
+                    ["IdentifierOrKeyword(\"a\")", &format!("Operator({:?})", op), "IdentifierOrKeyword(\"b\")"];
+                assert_eq!(tokenize(&format!("a{}b", op), options.clone()), expected, "{:?} {}", dialect, op);
+                assert_eq!(tokenize(&format!("a {} b", op), options), expected, "{:?} {}", dialect, op);
+            }
+        }
+
+        // The generic dialect only recognizes the common operators.
+        assert_eq!(tokenize("a->b", Options::default())[1..3], ["Operator(\"-\")", "Operator(\">\")"]);
+        assert_eq!(tokenize("a<=>b", Options::default())[1..3], ["Operator(\"<=\")", "Operator(\">\")"]);
+        assert_eq!(tokenize("a&&b", Options::default())[1..3], ["Operator(\"&\")", "Operator(\"&\")"]);
+
+        // The operators of a dialect are not recognized by the other dialects.
+        assert_eq!(tokenize("a#>b", Options::default()), ["IdentifierOrKeyword(\"a\")", "Comment(\"#>b\")"]);
+        assert_eq!(tokenize("a?|b", Options::default())[1..3], ["ParameterMarker(\"?\")", "Operator(\"|\")"]);
+        assert_eq!(tokenize("@@version", Options::default()), ["Variable(\"@@version\", System)"]);
+        let sql_server = Options { dialect: Dialect::SqlServer, ..Default::default() };
+        assert_eq!(tokenize("a<@p", sql_server)[1..], ["Operator(\"<\")", "Variable(\"@p\", Local)"]);
+        // An operator doesn't run into a comment.
+        let postgres = Options { dialect: Dialect::PostgreSql, ..Default::default() };
+        assert_eq!(tokenize("a||/*c*/b", postgres.clone())[1..3], ["Operator(\"||\")", "Comment(\"/*c*/\")"]);
+        assert_eq!(tokenize("a-|--c", postgres)[1..], ["Operator(\"-\")", "Operator(\"|\")", "Comment(\"--c\")"]);
+
+        // Custom operators are matched with the longest-match rule too.
+        let options = Options { custom_operators: vec!["<~>".to_string(), "~~".to_string()], ..Default::default() };
+        assert_eq!(
+            tokenize("a<~>b ~~* c", options)[1..4],
+            ["Operator(\"<~>\")", "IdentifierOrKeyword(\"b\")", "Operator(\"~~\")"]
+        );
+        // Custom operators which cannot be matched are ignored.
+        let custom_operators = ["", "DIV", "1+", "_x", " ~"].map(String::from).to_vec();
+        let tokenizer = Tokenizer::new("", Options { custom_operators, ..Default::default() });
+        assert_eq!(tokenizer.operators.len(), Tokenizer::new("", Options::default()).operators.len());
+    }
+
+    #[test]
+    fn test_psql() {
+        let options = Options { psql: true, ..Default::default() };
+        let tokenize = |input| -> Vec<Vec<String>> {
+            Tokenizer::new(input, options.clone())
+                .map(|s| {
+                    s.leading
+                        .iter()
+                        .chain(s.tokens.iter())
+                        .map(|t| format!("{:?}", t.value).split('(').next().unwrap().to_string() + ":" + &t.to_string())
+                        .collect()
+                })
+                .collect()
+        };
+        assert_eq!(
+            tokenize(
+                "\\set id 42 \n-- users\n\\connect db\n\
+                 SELECT * FROM :\"tbl\" WHERE (id = :id) \\gexec\nSELECT ':x'::text"
+            ),
+            [
+                vec!["MetaCommand:\\set id 42"],
+                vec!["Comment:-- users", "MetaCommand:\\connect db"],
+                vec![
+                    "IdentifierOrKeyword:SELECT",
+                    "Operator:*",
+                    "IdentifierOrKeyword:FROM",
+                    "Variable::\"tbl\"",
+                    "IdentifierOrKeyword:WHERE",
+                    "Any:(",
+                    "Fragment:id=:id",
+                    "Any:)"
+                ],
+                vec!["MetaCommand:\\gexec"],
+                vec![
+                    "IdentifierOrKeyword:SELECT",
+                    "QuotedIdentifierOrConstant:':x'",
+                    "Operator:::",
+                    "IdentifierOrKeyword:text"
+                ],
+            ]
+        );
+        assert_eq!(
+            tokenize("SELECT :'name', :{?name}, :'unterminated, a[1:2] \\"),
+            [vec![
+                "IdentifierOrKeyword:SELECT",
+                "Variable::'name'",
+                "Any:,",
+                "Variable::{?name}",
+                "Any:,",
+                "ParameterMarker::",
+                "QuotedIdentifierOrConstant:'unterminated, a[1:2] \\"
+            ],]
+        );
+        let statements: Vec<_> = Tokenizer::new("SELECT (1 \\g\n)", options.clone()).collect();
+        assert_eq!(statements.len(), 3);
+        assert!(statements[1].is_meta_command());
+        assert_eq!((statements[1].start().line, statements[1].start().column), (1, 11));
+        assert_eq!(statements[1].end().column, 12);
+        assert_eq!((statements[2].start().line, statements[2].start().column), (2, 1));
+
+        // Without the psql mode, the backslash and the variables are not recognized.
+        let statement = Tokenizer::new("\\set id :id", Options::default()).next().unwrap();
+        assert_eq!(statement.tokens.as_str_array(), ["\\", "set", "id", ":id"]);
+        assert!(statement.tokens[3].is_parameter_marker());
+    }
+
     #[test]
     fn test_quoted_identifier_or_constant() {
         assert_token!(r#"''"#, QuotedIdentifierOrConstant); // empty
@@ -15,8 +15,33 @@ pub enum TokenValue<'s> {
     ///
     /// - Single-line comments start with `--` or '#' and continue to the end of the line.
     /// - Multi-line comments start with `/*` and end with `*/`.
+    ///
+    /// Optimizer hints and executable comments are captured as [`TokenValue::OptimizerHint`] and
+    /// [`TokenValue::ExecutableComment`].
     Comment(&'s str),
 
+    /// An optimizer hint (Oracle, MySQL).
+    ///
+    /// ```sql
+    /// SELECT /*+ INDEX(t idx) */ * FROM t;
+    /// ```
+    ///
+    /// The text of the hint is given by [`Token::hint`].
+    OptimizerHint(&'s str),
+
+    /// A MySQL (`/*! ... */`) or MariaDB (`/*M! ... */`) executable comment, optionally versioned.
+    ///
+    /// ```sql
+    /// /*!50001 CREATE ALGORITHM=UNDEFINED */
+    /// ```
+    ///
+    /// By default the whole comment is captured as a single token. When
+    /// [`crate::Options::tokenize_executable_comments`] is enabled, the content of the comment is tokenized as SQL: the
+    /// opening of the comment (ex: `/*!50001`) and the closing `*/` are captured as `ExecutableComment` tokens
+    /// surrounding a [`TokenValue::Fragment`] with the tokens of the content. The version is given by
+    /// [`Token::mysql_version`].
+    ExecutableComment(&'s str),
+
     /// A quoted identifier or a non numeric constant.
     ///
     /// - *Quoted identifiers* are enclosed in double quotes (`"`). They are identifiers (like a table name, column name,
@@ -94,6 +119,9 @@ pub enum TokenValue<'s> {
     /// - Bitwise operators: `~`, `&`, `|`, `<<`, `>>`, `^`
     /// - PostgreSQL typecast operator: `::`
     /// - Regular expression operators: `~`, `~*`, `!~`, `!~*`
+    ///
+    /// Dialects have additional operators (ex: the JSON operators `->`, `->>` and `@>` of PostgreSQL) and custom
+    /// operators can be added with [`crate::Options::custom_operators`].
     Operator(&'s str),
 
     /// Mark the end of an SQL statement.
@@ -114,8 +142,90 @@ pub enum TokenValue<'s> {
     /// - Named Parameters with (`:`), (`$`) or (`@`) Syntax (ex: `:user_id`, `$user_id`, `@user_id`).
     ParameterMarker(&'s str),
 
-    /// A fragment of tokens, typically used for the content of parenthesis.
-    Fragment(Tokens<'s>),
+    /// A variable and its scope, see [`VariableScope`].
+    ///
+    /// - System variables (`@@version`, `@@session.sql_mode`, `@@ROWCOUNT`): all dialects.
+    /// - User variables (`@name`): MySQL.
+    /// - Local variables (`@name`): SQL Server.
+    /// - Session variables (`$name`): Snowflake and DuckDB.
+    /// - psql variables interpolated by the client (`:name`, `:'name'`, `:"name"` or `:{?name}`): only when
+    ///   [`crate::Options::psql`] is enabled.
+    ///
+    /// In the other dialects `@name` and `$name` are [`TokenValue::ParameterMarker`]s.
+    ///
+    /// ```sql
+    /// SELECT * FROM :"table" WHERE name = :'name' AND mode = @@session.sql_mode;
+    /// ```
+    Variable(&'s str, VariableScope),
+
+    /// A psql meta-command (ex: `\connect db`, `\set x 1`), from the backslash to the end of the line.
+    ///
+    /// Only captured when [`crate::Options::psql`] is enabled. A meta-command is always a statement of its own, see
+    /// [`crate::Statement::is_meta_command`].
+    MetaCommand(&'s str),
+
+    /// The inline data of a `COPY ... FROM STDIN` statement (or of a psql `\copy ... from stdin` meta-command).
+    ///
+    /// ```sql
+    /// COPY users (id, name) FROM stdin;
+    /// 1    John
+    /// 2    Jane
+    /// \.
+    /// ```
+    ///
+    /// The data starts on the line following the statement and ends with the `\.` line (included in the token) or at
+    /// the end of the input. It is the last token of the statement, see [`crate::Statement::copy_data`].
+    CopyData(&'s str),
+
+    /// A fragment of tokens: the content of parentheses, brackets, braces or of a MySQL executable comment, see
+    /// [`FragmentKind`].
+    ///
+    /// The opening and closing characters are not part of the fragment, they are the tokens surrounding it.
+    Fragment(Tokens<'s>, FragmentKind),
+}
+
+/// What a [`TokenValue::Fragment`] is the content of.
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FragmentKind {
+    /// Parentheses: `(...)`.
+    Parentheses,
+    /// Square brackets: `[...]` (ex: `arr[1:3]`, `ARRAY[1, 2]`, DuckDB lists).
+    Brackets,
+    /// Curly braces: `{...}` (ex: DuckDB struct literals, JDBC escapes such as `{fn now()}`).
+    Braces,
+    /// A MySQL executable comment: `/*! ... */`, see [`crate::Options::tokenize_executable_comments`].
+    ExecutableComment,
+}
+
+impl FragmentKind {
+    /// Returns the closing character of a bracket kind (`)`, `]` or `}`), `None` for an executable comment.
+    pub fn closing_char(&self) -> Option<char> {
+        match self {
+            FragmentKind::Parentheses => Some(')'),
+            FragmentKind::Brackets => Some(']'),
+            FragmentKind::Braces => Some('}'),
+            FragmentKind::ExecutableComment => None,
+        }
+    }
+}
+
+/// The scope of a [`TokenValue::Variable`].
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VariableScope {
+    /// A user-defined variable (MySQL `@name`).
+    User,
+    /// A system variable without an explicit scope (`@@version`, SQL Server `@@ROWCOUNT`).
+    System,
+    /// A global system variable (MySQL `@@global.name`).
+    Global,
+    /// A session variable (MySQL `@@session.name` or `@@local.name`, Snowflake or DuckDB `$name`).
+    Session,
+    /// A local variable (SQL Server `@name`).
+    Local,
+    /// A variable interpolated by the client before sending the statement (psql `:name`).
+    Client,
 }
 
 impl<'s> AsRef<str> for TokenValue<'s> {
@@ -123,13 +233,18 @@ impl<'s> AsRef<str> for TokenValue<'s> {
         match self {
             TokenValue::Any(value) => value,
             TokenValue::Comment(value) => value,
+            TokenValue::OptimizerHint(value) => value,
+            TokenValue::ExecutableComment(value) => value,
             TokenValue::QuotedIdentifierOrConstant(value) => value,
             TokenValue::Operator(value) => value,
             TokenValue::StatementDelimiter(value) => value,
             TokenValue::NumericConstant(value) => value,
             TokenValue::IdentifierOrKeyword(value) => value,
             TokenValue::ParameterMarker(value) => value,
-            TokenValue::Fragment(_) => {
+            TokenValue::Variable(value, _) => value,
+            TokenValue::MetaCommand(value) => value,
+            TokenValue::CopyData(value) => value,
+            TokenValue::Fragment(..) => {
                 panic!("TokenValue::Fragment does not contain a single &str")
             }
         }
@@ -165,12 +280,28 @@ impl<'s> Token<'s> {
         matches!(self.value, TokenValue::Comment(_))
     }
 
+    pub fn is_optimizer_hint(&self) -> bool {
+        matches!(self.value, TokenValue::OptimizerHint(_))
+    }
+
+    pub fn is_executable_comment(&self) -> bool {
+        matches!(self.value, TokenValue::ExecutableComment(_))
+    }
+
     pub fn is_quoted_identifier_or_constant(&self) -> bool {
         matches!(self.value, TokenValue::QuotedIdentifierOrConstant(_))
     }
 
     pub fn is_fragment(&self) -> bool {
-        matches!(self.value, TokenValue::Fragment(_))
+        matches!(self.value, TokenValue::Fragment(..))
+    }
+
+    /// Returns the kind of a fragment, `None` if the token is not a [`TokenValue::Fragment`].
+    pub fn fragment_kind(&self) -> Option<FragmentKind> {
+        match self.value {
+            TokenValue::Fragment(_, kind) => Some(kind),
+            _ => None,
+        }
     }
 
     pub fn is_statement_delimiter(&self) -> bool {
@@ -203,9 +334,67 @@ impl<'s> Token<'s> {
         matches!(self.value, TokenValue::ParameterMarker(_))
     }
 
+    pub fn is_variable(&self) -> bool {
+        matches!(self.value, TokenValue::Variable(..))
+    }
+
+    /// Returns the scope of a variable, `None` if the token is not a [`TokenValue::Variable`].
+    pub fn variable_scope(&self) -> Option<VariableScope> {
+        match self.value {
+            TokenValue::Variable(_, scope) => Some(scope),
+            _ => None,
+        }
+    }
+
+    pub fn is_meta_command(&self) -> bool {
+        matches!(self.value, TokenValue::MetaCommand(_))
+    }
+
+    pub fn is_copy_data(&self) -> bool {
+        matches!(self.value, TokenValue::CopyData(_))
+    }
+
+    /// Returns whether the token is the given keyword (case-insensitive).
+    ///
+    /// Because the tokenizer does not distinguish keywords from identifiers, any unquoted identifier matching the given
+    /// keyword is considered a match.
+    pub fn is_keyword(&self, keyword: &str) -> bool {
+        match &self.value {
+            TokenValue::IdentifierOrKeyword(value) => value.eq_ignore_ascii_case(keyword),
+            _ => false,
+        }
+    }
+
+    /// Returns the text of an optimizer hint, without the `/*+` and `*/` delimiters and the surrounding whitespace.
+    ///
+    /// Returns `None` if the token is not a [`TokenValue::OptimizerHint`].
+    pub fn hint(&self) -> Option<&'s str> {
+        match self.value {
+            TokenValue::OptimizerHint(value) => {
+                let text = &value[3..];
+                Some(text.strip_suffix("*/").unwrap_or(text).trim())
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the minimum server version of a versioned executable comment (ex: `50001` for `/*!50001 ... */`).
+    ///
+    /// Returns `None` if the token is not a [`TokenValue::ExecutableComment`] or if the comment is not versioned.
+    pub fn mysql_version(&self) -> Option<u32> {
+        match self.value {
+            TokenValue::ExecutableComment(value) => {
+                let text = value.strip_prefix("/*!").or(value.strip_prefix("/*M!"))?;
+                let len = text.chars().take_while(|c| c.is_ascii_digit()).count();
+                text[..len].parse().ok()
+            }
+            _ => None,
+        }
+    }
+
     pub fn children(&self) -> Option<&Tokens<'s>> {
         match &self.value {
-            TokenValue::Fragment(tokens) => Some(tokens),
+            TokenValue::Fragment(tokens, _) => Some(tokens),
             _ => None,
         }
     }
@@ -215,13 +404,18 @@ impl<'s> Token<'s> {
         match &self.value {
             TokenValue::Any(value) => vec![value],
             TokenValue::Comment(value) => vec![value],
+            TokenValue::OptimizerHint(value) => vec![value],
+            TokenValue::ExecutableComment(value) => vec![value],
             TokenValue::QuotedIdentifierOrConstant(value) => vec![value],
             TokenValue::StatementDelimiter(value) => vec![value],
             TokenValue::Operator(value) => vec![value],
             TokenValue::NumericConstant(value) => vec![value],
             TokenValue::IdentifierOrKeyword(value) => vec![value],
             TokenValue::ParameterMarker(value) => vec![value],
-            TokenValue::Fragment(tokens) => tokens.iter().flat_map(|t| t.as_str_array()).collect(),
+            TokenValue::Variable(value, _) => vec![value],
+            TokenValue::MetaCommand(value) => vec![value],
+            TokenValue::CopyData(value) => vec![value],
+            TokenValue::Fragment(tokens, _) => tokens.iter().flat_map(|t| t.as_str_array()).collect(),
         }
     }
 }
@@ -229,7 +423,7 @@ impl<'s> Token<'s> {
 impl std::fmt::Display for Token<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match &self.value {
-            TokenValue::Fragment(tokens) => {
+            TokenValue::Fragment(tokens, _) => {
                 for token in tokens.iter() {
                     write!(f, "{}", token)?;
                 }
@@ -254,19 +448,33 @@ impl<'s> Serialize for Token<'s> {
     where
         S: Serializer,
     {
-        let mut state = serializer.serialize_struct("Token", 4)?;
+        // The type, value, start and end fields, and the scope of a variable or the kind of a fragment.
+        let len = match self.value {
+            TokenValue::Variable(..) | TokenValue::Fragment(..) => 5,
+            _ => 4,
+        };
+        let mut state = serializer.serialize_struct("Token", len)?;
         match &self.value {
             TokenValue::Any(value) => ser_token_value!(state, Any, value),
             TokenValue::Comment(value) => ser_token_value!(state, Comment, value),
+            TokenValue::OptimizerHint(value) => ser_token_value!(state, OptimizerHint, value),
+            TokenValue::ExecutableComment(value) => ser_token_value!(state, ExecutableComment, value),
             TokenValue::QuotedIdentifierOrConstant(value) => ser_token_value!(state, QuotedIdentifierOrConstant, value),
             TokenValue::Operator(value) => ser_token_value!(state, Operator, value),
             TokenValue::StatementDelimiter(value) => ser_token_value!(state, StatementDelimiter, value),
             TokenValue::NumericConstant(value) => ser_token_value!(state, NumericConstant, value),
             TokenValue::IdentifierOrKeyword(value) => ser_token_value!(state, IdentifierOrKeyword, value),
             TokenValue::ParameterMarker(value) => ser_token_value!(state, ParameterMarker, value),
-            TokenValue::Fragment(tokens) => {
+            TokenValue::Variable(value, scope) => {
+                ser_token_value!(state, Variable, value);
+                state.serialize_field("scope", scope)?;
+            }
+            TokenValue::MetaCommand(value) => ser_token_value!(state, MetaCommand, value),
+            TokenValue::CopyData(value) => ser_token_value!(state, CopyData, value),
+            TokenValue::Fragment(tokens, kind) => {
                 state.serialize_field("type", "Fragment")?;
                 state.serialize_field("value", &tokens)?;
+                state.serialize_field("kind", kind)?;
             }
         }
         state.serialize_field("start", &self.start)?;
@@ -300,6 +508,67 @@ impl<'s> Tokens<'s> {
     }
 }
 
+// Returns the index of the first token at or after `from` which is not a comment (or an optimizer hint).
+pub(crate) fn next_significant(tokens: &[Token], from: usize) -> Option<usize> {
+    (from..tokens.len()).find(|&index| !is_comment_or_hint(&tokens[index]))
+}
+
+// Returns whether the token is a comment or an optimizer hint, which are both ignored when interpreting a statement.
+pub(crate) fn is_comment_or_hint(token: &Token) -> bool {
+    token.is_comment() || token.is_optimizer_hint()
+}
+
+// Returns whether the token is a string constant (as opposed to a quoted identifier).
+//
+// String constants are enclosed in single quotes (with an optional introducer: `E'...'`, `N'...'`, `_utf8'...'`, ...)
+// or dollar quotes (`$$...$$`), while quoted identifiers are enclosed in double quotes or backticks.
+pub(crate) fn is_string_constant(token: &Token) -> bool {
+    match token.value {
+        TokenValue::QuotedIdentifierOrConstant(value) => {
+            !(value.starts_with('"')
+                || value.starts_with('`')
+                || value.starts_with("U&\"")
+                || value.starts_with("u&\""))
+        }
+        _ => false,
+    }
+}
+
+// Returns all the tokens in the order they appear in the input, replacing fragments by the tokens they contain.
+pub(crate) fn leaves<'a, 's>(tokens: &'a [Token<'s>]) -> Vec<&'a Token<'s>> {
+    tokens
+        .iter()
+        .flat_map(|t| match &t.value {
+            TokenValue::Fragment(children, _) => leaves(children),
+            _ => vec![t],
+        })
+        .collect()
+}
+
+// Returns the first token of the given tokens, looking into fragments.
+pub(crate) fn first_leaf<'a, 's>(tokens: &'a [Token<'s>]) -> Option<&'a Token<'s>> {
+    tokens.iter().find_map(|t| match &t.value {
+        TokenValue::Fragment(children, _) => first_leaf(children),
+        _ => Some(t),
+    })
+}
+
+// Returns the last token of the given tokens, looking into fragments.
+pub(crate) fn last_leaf<'a, 's>(tokens: &'a [Token<'s>]) -> Option<&'a Token<'s>> {
+    tokens.iter().rev().find_map(|t| match &t.value {
+        TokenValue::Fragment(children, _) => last_leaf(children),
+        _ => Some(t),
+    })
+}
+
+// Returns the byte range of the input covered by the given tokens.
+//
+// The positions of a fragment token are not reliable to compute a range (a fragment is captured when reaching the end
+// of the parentheses block), so the range is computed from the first and last tokens nested in fragments.
+pub(crate) fn span(tokens: &[Token]) -> Option<std::ops::Range<usize>> {
+    Some(first_leaf(tokens)?.start.offset..last_leaf(tokens)?.end.offset)
+}
+
 // Implement Deref to delegate method calls to the inner Vec<Token<'s>>
 impl<'s> Deref for Tokens<'s> {
     type Target = Vec<Token<'s>>;
@@ -333,8 +602,12 @@ mod tests {
             Position::new(1, 8, 7)
         )
         .is_quoted_identifier_or_constant());
-        assert!(Token::new(TokenValue::Fragment(Tokens::new()), Position::new(1, 1, 0), Position::new(1, 1, 0))
-            .is_fragment());
+        assert!(Token::new(
+            TokenValue::Fragment(Tokens::new(), FragmentKind::Parentheses),
+            Position::new(1, 1, 0),
+            Position::new(1, 1, 0)
+        )
+        .is_fragment());
         assert!(Token::new(TokenValue::StatementDelimiter(";"), Position::new(1, 1, 0), Position::new(1, 1, 0))
             .is_statement_delimiter());
         assert!(Token::new(TokenValue::Operator("+"), Position::new(1, 1, 0), Position::new(1, 1, 0)).is_operator());
@@ -349,13 +622,31 @@ mod tests {
             .is_identifier_or_keyword());
         assert!(Token::new(TokenValue::ParameterMarker("?"), Position::new(1, 1, 0), Position::new(1, 1, 0))
             .is_parameter_marker());
+        assert!(Token::new(TokenValue::IdentifierOrKeyword("select"), Position::new(1, 1, 0), Position::new(1, 6, 5))
+            .is_keyword("SELECT"));
+        assert!(!Token::new(
+            TokenValue::IdentifierOrKeyword("selected"),
+            Position::new(1, 1, 0),
+            Position::new(1, 8, 7)
+        )
+        .is_keyword("SELECT"));
+        assert!(!Token::new(
+            TokenValue::QuotedIdentifierOrConstant("\"SELECT\""),
+            Position::new(1, 1, 0),
+            Position::new(1, 8, 7)
+        )
+        .is_keyword("SELECT"));
     }
 
     #[test]
     fn test_children() {
-        assert!(Token::new(TokenValue::Fragment(Tokens::new()), Position::new(1, 1, 0), Position::new(1, 1, 0))
-            .children()
-            .is_some());
+        assert!(Token::new(
+            TokenValue::Fragment(Tokens::new(), FragmentKind::Parentheses),
+            Position::new(1, 1, 0),
+            Position::new(1, 1, 0)
+        )
+        .children()
+        .is_some());
         assert!(Token::new(TokenValue::Any("SELECT"), Position::new(1, 1, 0), Position::new(1, 6, 5))
             .children()
             .is_none());